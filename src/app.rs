@@ -1,14 +1,12 @@
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
 use ratatui::layout::Rect;
 use ratatui_image::protocol::Protocol;
-use tokio::sync::Semaphore;
-use yomu::{ChapterData, ImageDataResponse, MangaData};
-
-/// Maximum number of concurrent background image downloads.
-pub const MAX_CONCURRENT_FETCHES: usize = 8;
-/// Maximum number of decoded pages to keep in memory at once.
-pub const MAX_CACHE_PAGES: usize = 20;
+use tokio::task::{AbortHandle, JoinSet};
+use crate::notifications::Notifications;
+use crate::settings::{GroupRef, Settings};
+use yomu::chapter::{VolumeGroup, group_by_volume, prefer_group};
+use yomu::{ChapterData, ChapterLoader, MangaData, MangaStatistics, SearchQuery, Tag};
 
 /// The main application state.
 pub struct App {
@@ -19,15 +17,58 @@ pub struct App {
     /// The results of the most recent manga search.
     pub search_result: Vec<MangaData>,
     /// The last search query performed to avoid redundant searches.
-    pub last_search_query: String,
+    pub last_search: Option<SearchQuery>,
+    /// Filters applied to searches, edited in the filter panel.
+    pub search_filters: SearchQuery,
+    /// Whether the filter panel on the Search screen has keyboard focus.
+    pub filter_panel_open: bool,
+    /// The highlighted row in the filter panel.
+    pub filter_cursor: usize,
+    /// The MangaDex tag catalogue, fetched the first time the tag browser opens.
+    pub tags: Vec<Tag>,
+    /// The highlighted tag in the tag browser.
+    pub tag_cursor: usize,
+    /// Total number of manga matching the last search, as reported by the API.
+    pub search_total: usize,
+    /// Offset of the next page of search results, or `None` once all pages are loaded.
+    pub search_next_offset: Option<usize>,
     /// The manga currently selected by the user.
     pub selected_manga: Option<MangaData>,
-    /// The list of chapters for the selected manga.
+    /// Rating and follow statistics for the selected manga.
+    pub manga_stats: Option<MangaStatistics>,
+    /// The decoded cover image of the selected manga.
+    pub cover: Option<image::DynamicImage>,
+    /// The cover encoded for the area it was last drawn in.
+    pub cover_proto: Option<(Rect, Protocol)>,
+    /// How many lines the detail screen's description is scrolled down.
+    pub detail_scroll: u16,
+    /// The user's preferred language for descriptions, taken from `LANG`.
+    pub ui_language: String,
+    /// Persistent user preferences.
+    pub settings: Settings,
+    /// Every chapter fetched for the selected manga, before group preferences.
+    pub all_chapters: Vec<ChapterData>,
+    /// The chapters shown in the chapter list, after group preferences.
     pub chapters: Vec<ChapterData>,
-    /// The index of the currently selected item in a list (search results or chapters).
+    /// `chapters` grouped into volumes, with duplicate chapter numbers merged.
+    pub volumes: Vec<VolumeGroup>,
+    /// Volumes collapsed in the chapter list, keyed by volume number.
+    pub collapsed_volumes: HashSet<Option<String>>,
+    /// The upload picked for each duplicated chapter number, as an index into
+    /// its `ChapterGroup::uploads`.
+    pub upload_choice: HashMap<String, usize>,
+    /// Whether the chapter language panel on the chapter list has keyboard focus.
+    pub language_panel_open: bool,
+    /// The highlighted row in the chapter language panel.
+    pub language_cursor: usize,
+    /// The language order when the panel was opened, to tell whether it changed.
+    pub language_snapshot: Option<Vec<String>>,
+    /// Index into `chapters` of the chapter being read.
+    pub current_chapter: Option<usize>,
+    /// The index of the currently selected item in a list (search results or chapter list rows).
     pub selected_index: usize,
-    /// Metadata for the current chapter's images.
-    pub image_data: Option<ImageDataResponse>,
+    /// Downloads and caches the pages of the chapter being read.
+    pub loader: Option<ChapterLoader>,
     /// The current page index when reading a chapter.
     pub current_page: usize,
     /// The currently decoded image for the left panel.
@@ -36,8 +77,6 @@ pub struct App {
     pub page_right: Option<image::DynamicImage>,
     /// The image rendering engine for the terminal.
     pub picker: Option<ratatui_image::picker::Picker>,
-    /// In-memory cache for decoded manga pages to enable instant navigation.
-    pub page_cache: HashMap<usize, image::DynamicImage>,
     /// Pre-built protocol cache keyed by (page_index, is_left_panel).
     /// Stores (area_used_for_encoding, protocol) so stale entries can be detected
     /// when the terminal is resized.
@@ -46,15 +85,81 @@ pub struct App {
     pub last_right_area: Rect,
     /// The left panel area from the most recent render frame.
     pub last_left_area: Rect,
-    /// Tracks which page indices have an in-flight or completed download
-    /// to prevent duplicate network requests.
-    pub fetched: std::collections::HashSet<usize>,
-    /// Tracks page indices whose download permanently failed so the UI can
-    /// show an error instead of a perpetual "Loading…" spinner.
-    pub failed: std::collections::HashSet<usize>,
-    /// Limits the number of concurrent background image downloads to avoid
-    /// flooding the CDN and triggering rate-limiting.
-    pub fetch_semaphore: Arc<Semaphore>,
+    /// Background protocol-build tasks for the open chapter.
+    pub proto_tasks: ProtoTasks,
+    /// The background API request the UI is waiting on, if any.
+    pub pending: Option<PendingRequest>,
+    /// ID handed to the next background API request.
+    pub next_request_id: u64,
+    /// Toasts and the history of errors shown to the user.
+    pub notifications: Notifications,
+    /// Whether the notification history is open over the current screen.
+    pub history_open: bool,
+    /// The highlighted entry in the notification history.
+    pub history_cursor: usize,
+}
+
+/// Background protocol-build tasks, tagged with the chapter they were
+/// started for.
+#[derive(Default)]
+pub struct ProtoTasks {
+    /// Identifies the chapter the tasks are working on. Bumped on every
+    /// chapter switch so late protocols from the previous one are discarded.
+    pub generation: u64,
+    /// Tasks of the current generation.
+    pub set: JoinSet<()>,
+}
+
+impl ProtoTasks {
+    /// Aborts every task and starts a new generation.
+    pub fn restart(&mut self) {
+        self.set.abort_all();
+        self.generation += 1;
+    }
+}
+
+/// A background MangaDex API request whose result has not arrived yet.
+pub struct PendingRequest {
+    /// Matches the ID on the reply, so replies to superseded requests are dropped.
+    pub id: u64,
+    /// What the request is fetching.
+    pub kind: RequestKind,
+    /// When the request was started, to animate the spinner.
+    pub started: Instant,
+    /// Aborts the task running the request.
+    pub handle: AbortHandle,
+}
+
+/// The kinds of background API request.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RequestKind {
+    Search,
+    Tags,
+    MangaDetail,
+    Chapters,
+    ImageData,
+}
+
+impl RequestKind {
+    /// Short description shown next to the spinner.
+    pub fn label(&self) -> &'static str {
+        match self {
+            RequestKind::Search => "Searching…",
+            RequestKind::Tags => "Loading tags…",
+            RequestKind::MangaDetail => "Loading manga…",
+            RequestKind::Chapters => "Loading chapters…",
+            RequestKind::ImageData => "Opening chapter…",
+        }
+    }
+}
+
+/// A row of the grouped chapter list.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ChapterRow {
+    /// A volume header, by index into `App::volumes`.
+    Volume(usize),
+    /// A chapter, by volume index and index into that volume's chapters.
+    Chapter(usize, usize),
 }
 
 /// The different screens in the application.
@@ -63,6 +168,10 @@ pub enum AppScreen {
     Splash,
     /// The search input and results screen.
     Search,
+    /// The tag catalogue, for picking tags to include or exclude in a search.
+    TagBrowser,
+    /// Cover, description and metadata for a selected manga.
+    MangaDetail,
     /// The list of chapters for a selected manga.
     ChapterList,
     /// The screen for reading a specific chapter.
@@ -76,22 +185,204 @@ impl App {
             screen: AppScreen::Splash,
             search_input: String::new(),
             search_result: Vec::new(),
-            last_search_query: String::new(),
+            last_search: None,
+            search_filters: SearchQuery::default(),
+            filter_panel_open: false,
+            filter_cursor: 0,
+            tags: Vec::new(),
+            tag_cursor: 0,
+            search_total: 0,
+            search_next_offset: None,
             selected_manga: None,
+            manga_stats: None,
+            cover: None,
+            cover_proto: None,
+            detail_scroll: 0,
+            ui_language: detect_ui_language(),
+            settings: Settings::load(),
+            all_chapters: Vec::new(),
             chapters: Vec::new(),
-            image_data: None,
+            volumes: Vec::new(),
+            collapsed_volumes: HashSet::new(),
+            upload_choice: HashMap::new(),
+            language_panel_open: false,
+            language_cursor: 0,
+            language_snapshot: None,
+            current_chapter: None,
+            loader: None,
             selected_index: 0,
             current_page: 0,
             page_left: None,
             page_right: None,
             picker: None,
-            page_cache: HashMap::new(),
             proto_cache: HashMap::new(),
             last_right_area: Rect::default(),
             last_left_area: Rect::default(),
-            fetched: std::collections::HashSet::new(),
-            failed: std::collections::HashSet::new(),
-            fetch_semaphore: Arc::new(Semaphore::new(MAX_CONCURRENT_FETCHES)),
+            proto_tasks: ProtoTasks::default(),
+            pending: None,
+            next_request_id: 0,
+            notifications: Notifications::default(),
+            history_open: false,
+            history_cursor: 0,
+        }
+    }
+
+    /// Builds the search query from the current input and filter panel.
+    pub fn pending_query(&self) -> SearchQuery {
+        let mut query = self.search_filters.clone();
+        if !self.search_input.is_empty() {
+            query.title = Some(self.search_input.clone());
         }
+        query
     }
+
+    /// Toggles the tag at `idx` in the include (or exclude) list.
+    ///
+    /// A tag is never in both lists at once.
+    pub fn toggle_tag(&mut self, idx: usize, include: bool) {
+        let Some(tag) = self.tags.get(idx) else {
+            return;
+        };
+        let filters = &mut self.search_filters;
+        let (target, other) = if include {
+            (&mut filters.included_tags, &mut filters.excluded_tags)
+        } else {
+            (&mut filters.excluded_tags, &mut filters.included_tags)
+        };
+        other.retain(|id| id != &tag.id);
+        if let Some(pos) = target.iter().position(|id| id == &tag.id) {
+            target.remove(pos);
+        } else {
+            target.push(tag.id.clone());
+        }
+    }
+
+    /// Cycles the tag at `idx` through neutral, included and excluded.
+    pub fn cycle_tag(&mut self, idx: usize) {
+        let Some(tag) = self.tags.get(idx) else {
+            return;
+        };
+        // Neutral tags become included; included tags move to excluded;
+        // excluded tags are removed again.
+        let filters = &self.search_filters;
+        let neutral =
+            !filters.included_tags.contains(&tag.id) && !filters.excluded_tags.contains(&tag.id);
+        self.toggle_tag(idx, neutral);
+    }
+
+    /// The preferred scanlation group for the selected manga, if any.
+    pub fn preferred_group(&self) -> Option<&GroupRef> {
+        let manga_id = &self.selected_manga.as_ref()?.id;
+        self.settings.preferred_groups.get(manga_id)
+    }
+
+    /// Rebuilds the visible chapter list from `all_chapters`, applying the
+    /// blocklist and the selected manga's preferred group.
+    pub fn refresh_chapters(&mut self) {
+        let blocked = self.settings.blocked_group_ids();
+        let chapters: Vec<ChapterData> = self
+            .all_chapters
+            .iter()
+            .filter(|c| !c.scanlation_groups().any(|g| blocked.contains(&g.id)))
+            .cloned()
+            .collect();
+        self.chapters = match self.preferred_group() {
+            Some(group) => prefer_group(chapters, &group.id),
+            None => chapters,
+        };
+        self.volumes = group_by_volume(&self.chapters);
+        self.selected_index = self
+            .selected_index
+            .min(self.chapter_rows().len().saturating_sub(1));
+    }
+
+    /// The rows of the chapter list: a header per volume, followed by its
+    /// chapters unless the volume is collapsed.
+    pub fn chapter_rows(&self) -> Vec<ChapterRow> {
+        let mut rows = Vec::new();
+        for (v, volume) in self.volumes.iter().enumerate() {
+            rows.push(ChapterRow::Volume(v));
+            if !self.collapsed_volumes.contains(&volume.volume) {
+                rows.extend((0..volume.chapters.len()).map(|c| ChapterRow::Chapter(v, c)));
+            }
+        }
+        rows
+    }
+
+    /// Index into `chapters` of the upload picked for the chapter at (`volume`, `entry`).
+    pub fn chosen_upload(&self, volume: usize, entry: usize) -> Option<usize> {
+        let group = self.volumes.get(volume)?.chapters.get(entry)?;
+        let choice = group
+            .chapter
+            .as_ref()
+            .and_then(|n| self.upload_choice.get(n))
+            .copied()
+            .unwrap_or(0);
+        group.uploads.get(choice).or(group.uploads.first()).copied()
+    }
+
+    /// Drops the chapter loader, which aborts its downloads, cancels every
+    /// protocol build and empties the page state.
+    pub fn close_chapter(&mut self) {
+        self.loader = None;
+        self.proto_tasks.restart();
+        self.current_page = 0;
+        self.proto_cache.clear();
+        self.page_left = None;
+        self.page_right = None;
+    }
+
+    /// Saves the settings file, reporting a failure as a notification.
+    pub fn save_settings(&mut self) {
+        if let Err(e) = self.settings.save() {
+            self.notifications.error("Could not save settings", e);
+        }
+    }
+
+    /// Aborts the pending request, if any. Returns `true` if one was cancelled.
+    pub fn cancel_request(&mut self) -> bool {
+        match self.pending.take() {
+            Some(pending) => {
+                pending.handle.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns `true` if a request of `kind` is in flight.
+    pub fn is_pending(&self, kind: RequestKind) -> bool {
+        self.pending.as_ref().is_some_and(|p| p.kind == kind)
+    }
+
+    /// The title of `manga` in the user's preferred title language.
+    pub fn manga_title<'a>(&self, manga: &'a MangaData) -> &'a str {
+        let languages: Vec<&str> = self
+            .settings
+            .title_languages
+            .iter()
+            .map(|l| l.as_str())
+            .collect();
+        manga.localized_title(&languages).unwrap_or("Unknown Title")
+    }
+
+    /// Index into `chapters` of the chapter under the cursor, if the cursor is on a chapter row.
+    pub fn selected_chapter(&self) -> Option<usize> {
+        match self.chapter_rows().get(self.selected_index)? {
+            ChapterRow::Volume(_) => None,
+            ChapterRow::Chapter(v, c) => self.chosen_upload(*v, *c),
+        }
+    }
+}
+
+/// Reads the user's language from `LANG` (e.g. `es_ES.UTF-8` becomes `es`),
+/// falling back to English.
+fn detect_ui_language() -> String {
+    std::env::var("LANG")
+        .ok()
+        .and_then(|lang| {
+            let code = lang.split(['_', '.']).next()?.to_lowercase();
+            (!code.is_empty() && code != "c" && code != "posix").then_some(code)
+        })
+        .unwrap_or_else(|| "en".to_string())
 }
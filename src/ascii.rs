@@ -1,5 +1,6 @@
 use crate::error::Result;
-use image::{imageops::FilterType, load_from_memory};
+use crate::image::DecodeLimits;
+use image::imageops::FilterType;
 /// Converts raw image bytes into an ASCII art string.
 ///
 /// This function resizes the image to the specified width and height,
@@ -8,22 +9,29 @@ use image::{imageops::FilterType, load_from_memory};
 /// # Example
 ///
 /// ```rust
+/// use std::io::Cursor;
 /// use yomu::ascii::convert_to_ascii;
 ///
-/// let bytes = include_bytes!("../tests/test_image.jpg");
-/// let ascii = convert_to_ascii(bytes, 80, 40)?;
+/// // A black-to-white gradient, encoded as PNG.
+/// let gradient = image::GrayImage::from_fn(8, 8, |x, _| image::Luma([(x * 36) as u8]));
+/// let mut bytes = Vec::new();
+/// gradient.write_to(&mut Cursor::new(&mut bytes), image::ImageFormat::Png)?;
+///
+/// let ascii = convert_to_ascii(&bytes, 8, 8)?;
 /// println!("{}", ascii);
+/// assert!(!ascii.is_empty());
 /// # Ok::<(), yomu::error::YomuError>(())
 /// ```
 pub fn convert_to_ascii(bytes: &[u8], width: u32, height: u32) -> Result<String> {
     // Professional 70-character grayscale set (ordered from dark to light)
     let ascii_chars = "$@B%8&WM#*oahkbdpqwmZO0QLCJUYXzcvunxrjft/\\|()1{}[]?-_+~<>i!lI;:,\"^`'. ";
-    
-    let img = load_from_memory(bytes)?
+
+    let img = DecodeLimits::default()
+        .decode(bytes)?
         .resize(width, height / 2, FilterType::Triangle)
         .to_luma8();
 
-    let mut ascii_art = String::with_capacity((img.len() as usize + height as usize) * 2);
+    let mut ascii_art = String::with_capacity((img.len() + height as usize) * 2);
     let char_list: Vec<char> = ascii_chars.chars().collect();
     let num_chars = char_list.len();
 
@@ -44,6 +52,7 @@ mod test {
     use crate::{ascii::convert_to_ascii, client::MangaDexClient};
 
     #[tokio::test]
+    #[ignore = "requires live network access"]
     async fn test_img_download() {
         let client = MangaDexClient::new().unwrap();
         let img_client = client.image_client();
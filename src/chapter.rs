@@ -1,56 +1,274 @@
 use crate::client::MangaDexClient;
 use crate::error::Result;
+use crate::ratelimit::Endpoint;
+use crate::relationship::{Include, Relationship, RelationshipKind, include_pairs};
 use serde::Deserialize;
+use std::collections::HashMap;
 
 /// A client for fetching chapter-related information.
-pub struct ChapterClient<'mangaclient> {
-    /// Reference to the parent `MangaDexClient`.
-    pub client: &'mangaclient MangaDexClient,
+#[derive(Clone)]
+pub struct ChapterClient {
+    /// The parent `MangaDexClient` (a cheap clone sharing its connection pool).
+    pub client: MangaDexClient,
+    /// Related entities to expand inline in every response.
+    pub includes: Vec<Include>,
+    /// Scanlation group IDs whose chapters are dropped from every feed.
+    pub blocked_groups: Vec<String>,
 }
 
-impl<'mangaclient> ChapterClient<'mangaclient> {
-    /// Fetches the feed (chapters) for a specific manga ID.
+impl ChapterClient {
+    /// Requests that the given related entities be expanded inline, so their
+    /// attributes are available on each result's `relationships`.
+    pub fn with_includes(mut self, includes: &[Include]) -> Self {
+        self.includes.extend_from_slice(includes);
+        self
+    }
+
+    /// Drops chapters credited to any of the given scanlation group IDs.
+    ///
+    /// The filter runs on the client after each feed page arrives, so it
+    /// applies to both [`fetch_chapter`](Self::fetch_chapter) and
+    /// [`feed_pages`](Self::feed_pages).
+    pub fn with_blocked_groups(mut self, group_ids: &[String]) -> Self {
+        self.blocked_groups.extend_from_slice(group_ids);
+        self
+    }
+
+    /// Fetches the complete feed (chapters) for a specific manga ID.
+    ///
+    /// Walks every page of `/manga/{id}/feed` until `offset + limit >= total`,
+    /// so long-running series are returned in full.
+    ///
+    /// `languages` is in priority order: each chapter number is taken from
+    /// the first language that has it (see [`merge_languages`]). An empty
+    /// list means English.
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,no_run
     /// # use yomu::MangaDexClient;
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// # let client = MangaDexClient::new()?;
     /// let manga_id = "a77742b1-befd-49a4-bff5-1ad4e6b0ef7b";
-    /// let chapters = client.chapter_client().fetch_chapter(manga_id, Some("en")).await?;
+    /// let chapters = client.chapter_client().fetch_chapter(manga_id, &["en", "es-la"]).await?;
     /// # Ok(())
     /// # }
     /// ```
     pub async fn fetch_chapter(
         &self,
         manga_id: &str,
-        language: Option<&str>,
+        languages: &[&str],
     ) -> Result<Vec<ChapterData>> {
-        let resp: reqwest::Response = self
+        let mut feed = self.feed_pages(manga_id, languages);
+        let mut chapters = Vec::new();
+        while let Some(page) = feed.next_page().await? {
+            chapters.extend(page);
+        }
+        Ok(merge_languages(chapters, languages))
+    }
+
+    /// Returns a pager that walks the feed for a manga one page at a time.
+    ///
+    /// Use this instead of [`fetch_chapter`](Self::fetch_chapter) to start
+    /// showing chapters before the whole feed has arrived. Pages contain
+    /// every requested language; pass the collected feed to
+    /// [`merge_languages`] to apply the priority order.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use yomu::MangaDexClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = MangaDexClient::new()?;
+    /// let chapter_client = client.chapter_client();
+    /// let mut feed = chapter_client.feed_pages("a1c7c817-4e59-43b7-9365-09675a149a6f", &["en"]);
+    /// while let Some(page) = feed.next_page().await? {
+    ///     println!("received {} chapters", page.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn feed_pages<'feed>(
+        &'feed self,
+        manga_id: &'feed str,
+        languages: &'feed [&'feed str],
+    ) -> ChapterFeed<'feed> {
+        ChapterFeed {
+            chapter_client: self,
+            manga_id,
+            languages,
+            offset: 0,
+            total: None,
+        }
+    }
+
+    /// Fetches a single page of the feed starting at `offset`.
+    async fn fetch_feed_page(
+        &self,
+        manga_id: &str,
+        languages: &[&str],
+        offset: usize,
+    ) -> Result<ChapterResponse> {
+        let languages = if languages.is_empty() {
+            &["en"][..]
+        } else {
+            languages
+        };
+        let request = self
             .client
             .http_client()
-            .get(&format!("{}/manga/{}/feed", self.client.base_url, manga_id))
+            .get(format!(
+                "{}/manga/{}/feed",
+                self.client.base_url(),
+                manga_id
+            ))
+            .query(
+                &languages
+                    .iter()
+                    .map(|l| ("translatedLanguage[]", *l))
+                    .collect::<Vec<_>>(),
+            )
             .query(&[
-                ("translatedLanguage[]", language.unwrap_or("en")),
                 ("order[chapter]", "asc"),
+                ("limit", &FEED_PAGE_LIMIT.to_string()),
+                ("offset", &offset.to_string()),
             ])
-            .send()
-            .await?
-            .error_for_status()?;
-        let resp_json = resp.json::<ChapterResponse>().await?;
-        let filtered_json: Vec<ChapterData> = resp_json
+            .query(&include_pairs(&self.includes));
+        let resp = self.client.send(Endpoint::Api, request).await?;
+        Ok(resp.json::<ChapterResponse>().await?)
+    }
+}
+
+/// Maximum number of chapters MangaDex returns per feed request.
+const FEED_PAGE_LIMIT: usize = 500;
+
+/// A pager over a manga's chapter feed, created by [`ChapterClient::feed_pages`].
+pub struct ChapterFeed<'feed> {
+    chapter_client: &'feed ChapterClient,
+    manga_id: &'feed str,
+    languages: &'feed [&'feed str],
+    offset: usize,
+    total: Option<usize>,
+}
+
+impl ChapterFeed<'_> {
+    /// Fetches the next page of chapters, or returns `None` once the feed is exhausted.
+    ///
+    /// Chapters without any pages (e.g. external links) and chapters from
+    /// blocked groups are filtered out, so a returned page may be empty even
+    /// though more pages follow.
+    pub async fn next_page(&mut self) -> Result<Option<Vec<ChapterData>>> {
+        if self.total.is_some_and(|total| self.offset >= total) {
+            return Ok(None);
+        }
+        let resp = self
+            .chapter_client
+            .fetch_feed_page(self.manga_id, self.languages, self.offset)
+            .await?;
+        self.total = Some(resp.total);
+        // Guard against a zero limit so a misbehaving server cannot loop us forever.
+        self.offset = resp.offset + resp.limit.max(1);
+        // An empty page also ends the feed, whatever total the server claims.
+        if resp.data.is_empty() {
+            self.total = Some(resp.total.min(self.offset));
+        }
+        let filtered = resp
             .data
             .into_iter()
-            .filter(|chapter| match chapter.attributes.pages {
-                Some(pages) => pages > 0,
-                None => false,
+            .filter(|chapter| chapter.attributes.pages.is_some_and(|pages| pages > 0))
+            .filter(|chapter| {
+                !chapter
+                    .scanlation_groups()
+                    .any(|g| self.chapter_client.blocked_groups.contains(&g.id))
             })
             .collect();
-        Ok(filtered_json)
+        Ok(Some(filtered))
+    }
+
+    /// Total number of chapters reported by the API, known after the first page.
+    pub fn total(&self) -> Option<usize> {
+        self.total
+    }
+}
+
+/// The chapters of one volume, as built by [`group_by_volume`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeGroup {
+    /// Volume number, or `None` for chapters not assigned to a volume.
+    pub volume: Option<String>,
+    /// Chapter numbers in this volume, in reading order.
+    pub chapters: Vec<ChapterGroup>,
+}
+
+/// Every upload of a single chapter number.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChapterGroup {
+    /// Chapter number, or `None` for unnumbered chapters such as oneshots.
+    pub chapter: Option<String>,
+    /// Indices into the slice passed to [`group_by_volume`], in feed order.
+    pub uploads: Vec<usize>,
+}
+
+/// Groups a feed into volumes and merges duplicate chapter numbers.
+///
+/// Uploads of the same chapter number collapse into one [`ChapterGroup`],
+/// filed under the first volume any upload assigns it to. Volumes are sorted
+/// numerically with "no volume" last; unnumbered chapters are never merged.
+pub fn group_by_volume(chapters: &[ChapterData]) -> Vec<VolumeGroup> {
+    // Some groups leave the volume blank, so take it from whichever upload has one.
+    let mut volume_of: HashMap<&str, &str> = HashMap::new();
+    for c in chapters {
+        if let (Some(number), Some(volume)) = (&c.attributes.chapter, &c.attributes.volume) {
+            volume_of.entry(number.as_str()).or_insert(volume.as_str());
+        }
     }
+
+    let mut volumes: Vec<VolumeGroup> = Vec::new();
+    for (idx, c) in chapters.iter().enumerate() {
+        let number = c.attributes.chapter.clone();
+        let volume = match &number {
+            Some(n) => volume_of.get(n.as_str()).map(|v| v.to_string()),
+            None => c.attributes.volume.clone(),
+        };
+        let group = match volumes.iter().position(|v| v.volume == volume) {
+            Some(pos) => &mut volumes[pos],
+            None => {
+                volumes.push(VolumeGroup {
+                    volume,
+                    chapters: Vec::new(),
+                });
+                volumes.last_mut().expect("volume was just pushed")
+            }
+        };
+        match group
+            .chapters
+            .iter_mut()
+            .find(|g| number.is_some() && g.chapter == number)
+        {
+            Some(existing) => existing.uploads.push(idx),
+            None => group.chapters.push(ChapterGroup {
+                chapter: number,
+                uploads: vec![idx],
+            }),
+        }
+    }
+
+    let sort_key = |n: &Option<String>| {
+        n.as_deref()
+            .and_then(|n| n.parse::<f64>().ok())
+            .unwrap_or(f64::MAX)
+    };
+    volumes.sort_by(|a, b| sort_key(&a.volume).total_cmp(&sort_key(&b.volume)));
+    for volume in &mut volumes {
+        volume
+            .chapters
+            .sort_by(|a, b| sort_key(&a.chapter).total_cmp(&sort_key(&b.chapter)));
+    }
+    volumes
 }
+
 /// Response from the MangaDex API for a chapter feed request.
 #[derive(Deserialize)]
 pub struct ChapterResponse {
@@ -67,8 +285,9 @@ pub struct ChapterResponse {
     /// Total number of items matching the query.
     pub total: usize,
 }
+
 /// Data representation of a single chapter.
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 pub struct ChapterData {
     /// Unique identifier for the chapter.
     pub id: String,
@@ -77,9 +296,105 @@ pub struct ChapterData {
     pub type_: String,
     /// Attributes containing chapter information.
     pub attributes: ChapterAttributes,
+    /// Related entities such as the scanlation group, uploader and parent manga.
+    #[serde(default)]
+    pub relationships: Vec<Relationship>,
 }
+
+impl ChapterData {
+    /// Returns the ID of the manga this chapter belongs to.
+    pub fn manga_id(&self) -> Option<&str> {
+        self.relationships
+            .iter()
+            .find(|r| r.kind == RelationshipKind::Manga)
+            .map(|r| r.id.as_str())
+    }
+
+    /// Returns the scanlation groups credited for this chapter.
+    ///
+    /// Attributes are only present when requested with [`Include::ScanlationGroup`].
+    pub fn scanlation_groups(&self) -> impl Iterator<Item = &Relationship> {
+        self.relationships
+            .iter()
+            .filter(|r| r.kind == RelationshipKind::ScanlationGroup)
+    }
+
+    /// Returns the names of the credited scanlation groups, if expanded.
+    pub fn scanlation_group_names(&self) -> Vec<&str> {
+        self.scanlation_groups()
+            .filter_map(|g| g.as_scanlation_group())
+            .map(|g| g.name.as_str())
+            .collect()
+    }
+
+    /// Returns `true` if `group_id` is credited for this chapter.
+    pub fn is_by_group(&self, group_id: &str) -> bool {
+        self.scanlation_groups().any(|g| g.id == group_id)
+    }
+}
+
+/// Hides uploads that compete with a preferred scanlation group.
+///
+/// For every chapter number the preferred group has uploaded, uploads of the
+/// same number by other groups are removed. Chapters the preferred group has
+/// not covered are kept, so the feed has no gaps; unnumbered chapters (e.g.
+/// oneshots and extras) are unrelated to each other and always kept.
+pub fn prefer_group(chapters: Vec<ChapterData>, group_id: &str) -> Vec<ChapterData> {
+    let covered: Vec<String> = chapters
+        .iter()
+        .filter(|c| c.is_by_group(group_id))
+        .filter_map(|c| c.attributes.chapter.clone())
+        .collect();
+    chapters
+        .into_iter()
+        .filter(|c| {
+            c.is_by_group(group_id)
+                || c.attributes
+                    .chapter
+                    .as_ref()
+                    .is_none_or(|number| !covered.contains(number))
+        })
+        .collect()
+}
+
+/// Keeps, for each chapter number, only the uploads in the most preferred language.
+///
+/// `languages` is in priority order, so a chapter missing from the first
+/// language is filled from the next one that has it. Uploads in languages
+/// not listed rank last; unnumbered chapters are always kept.
+pub fn merge_languages(chapters: Vec<ChapterData>, languages: &[&str]) -> Vec<ChapterData> {
+    let rank = |c: &ChapterData| {
+        c.attributes
+            .translated_language
+            .as_deref()
+            .and_then(|l| languages.iter().position(|p| *p == l))
+            .unwrap_or(languages.len())
+    };
+    let mut best: HashMap<&str, usize> = HashMap::new();
+    for c in &chapters {
+        if let Some(number) = c.attributes.chapter.as_deref() {
+            let entry = best.entry(number).or_insert(usize::MAX);
+            *entry = (*entry).min(rank(c));
+        }
+    }
+    let keep: Vec<bool> = chapters
+        .iter()
+        .map(|c| {
+            c.attributes
+                .chapter
+                .as_deref()
+                .is_none_or(|number| best[number] == rank(c))
+        })
+        .collect();
+    chapters
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(c, keep)| keep.then_some(c))
+        .collect()
+}
+
 /// Attributes associated with a chapter.
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 pub struct ChapterAttributes {
     /// Volume number (if applicable).
     pub volume: Option<String>,
@@ -96,3 +411,175 @@ pub struct ChapterAttributes {
     /// Number of pages in the chapter.
     pub pages: Option<usize>,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::retry::RetryPolicy;
+    use crate::test_support::serve;
+
+    fn chapter(number: &str, group_id: &str) -> ChapterData {
+        chapter_in(None, number, group_id)
+    }
+
+    fn chapter_in(volume: Option<&str>, number: &str, group_id: &str) -> ChapterData {
+        serde_json::from_value(chapter_json(volume, number, group_id)).unwrap()
+    }
+
+    fn chapter_json(volume: Option<&str>, number: &str, group_id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": format!("{number}-{group_id}"),
+            "type": "chapter",
+            "attributes": {
+                "volume": volume,
+                "chapter": number,
+                "title": null,
+                "translatedLanguage": "en",
+                "isUnavailable": false,
+                "pages": 20
+            },
+            "relationships": [{ "id": group_id, "type": "scanlation_group" }]
+        })
+    }
+
+    /// An HTTP response carrying one feed page with the given chapter numbers.
+    fn feed_page(offset: usize, limit: usize, total: usize, numbers: &[&str]) -> String {
+        let data: Vec<_> = numbers.iter().map(|n| chapter_json(None, n, "a")).collect();
+        let body = serde_json::json!({
+            "result": "ok",
+            "response": "collection",
+            "data": data,
+            "limit": limit,
+            "offset": offset,
+            "total": total
+        })
+        .to_string();
+        format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{body}",
+            body.len()
+        )
+    }
+
+    fn local_client(base_url: String) -> ChapterClient {
+        MangaDexClient::builder()
+            .base_url(base_url)
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .unwrap()
+            .chapter_client()
+    }
+
+    #[tokio::test]
+    async fn fetch_chapter_walks_every_feed_page() {
+        // The second page is short and ends the feed, so no third request is made.
+        let (base_url, server) = serve(vec![
+            feed_page(0, 2, 3, &["1", "2"]),
+            feed_page(2, 2, 3, &["3"]),
+        ])
+        .await;
+        let chapters = local_client(base_url)
+            .fetch_chapter("m", &["en"])
+            .await
+            .unwrap();
+        let ids: Vec<&str> = chapters.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, ["1-a", "2-a", "3-a"]);
+
+        let requests = server.await.unwrap();
+        assert!(requests[0].starts_with("GET /manga/m/feed?"));
+        assert!(requests[0].contains("offset=0"));
+        assert!(requests[1].contains("offset=2"));
+    }
+
+    #[tokio::test]
+    async fn feed_pages_stops_at_total() {
+        let (base_url, server) = serve(vec![feed_page(0, 500, 0, &[])]).await;
+        let chapter_client = local_client(base_url);
+        let mut feed = chapter_client.feed_pages("m", &["en"]);
+        assert_eq!(feed.next_page().await.unwrap().map(|p| p.len()), Some(0));
+        assert_eq!(feed.total(), Some(0));
+        assert!(feed.next_page().await.unwrap().is_none());
+        assert_eq!(server.await.unwrap().len(), 1);
+
+        let (base_url, server) = serve(vec![
+            feed_page(0, 2, 4, &["1", "2"]),
+            feed_page(2, 2, 4, &["3", "4"]),
+        ])
+        .await;
+        let chapter_client = local_client(base_url);
+        let mut feed = chapter_client.feed_pages("m", &["en"]);
+        let mut pages = 0;
+        while feed.next_page().await.unwrap().is_some() {
+            pages += 1;
+        }
+        assert_eq!(pages, 2);
+        assert_eq!(feed.total(), Some(4));
+        assert_eq!(server.await.unwrap().len(), 2);
+    }
+
+    fn unnumbered(id: &str, group_id: &str) -> ChapterData {
+        let mut chapter = chapter("0", group_id);
+        chapter.id = id.to_string();
+        chapter.attributes.chapter = None;
+        chapter
+    }
+
+    #[test]
+    fn prefer_group_hides_competing_uploads_only() {
+        let chapters = vec![
+            chapter("1", "a"),
+            chapter("1", "b"),
+            chapter("2", "b"),
+            chapter("3", "a"),
+            chapter("3", "c"),
+            unnumbered("oneshot", "a"),
+            unnumbered("extra", "b"),
+        ];
+        let ids: Vec<String> = prefer_group(chapters, "a")
+            .into_iter()
+            .map(|c| c.id)
+            .collect();
+        assert_eq!(ids, ["1-a", "2-b", "3-a", "oneshot", "extra"]);
+    }
+
+    #[test]
+    fn group_by_volume_merges_duplicates_and_puts_no_volume_last() {
+        let chapters = vec![
+            chapter("0", "a"),
+            chapter_in(Some("2"), "10", "a"),
+            chapter_in(Some("1"), "1", "a"),
+            chapter("1", "b"),
+            chapter_in(Some("1"), "2", "a"),
+        ];
+        let volumes = group_by_volume(&chapters);
+        let volume_names: Vec<Option<&str>> = volumes.iter().map(|v| v.volume.as_deref()).collect();
+        assert_eq!(volume_names, [Some("1"), Some("2"), None]);
+
+        // Chapter 1 from group "b" had no volume but joins group "a"'s upload in volume 1.
+        let first = &volumes[0].chapters[0];
+        assert_eq!(first.chapter.as_deref(), Some("1"));
+        assert_eq!(first.uploads, [2, 3]);
+        assert_eq!(volumes[0].chapters[1].chapter.as_deref(), Some("2"));
+        assert_eq!(volumes[2].chapters[0].uploads, [0]);
+    }
+
+    #[test]
+    fn merge_languages_fills_gaps_from_later_languages() {
+        let in_language = |number: &str, language: &str| {
+            let mut c = chapter(number, language);
+            c.attributes.translated_language = Some(language.to_string());
+            c
+        };
+        let chapters = vec![
+            in_language("1", "es"),
+            in_language("1", "en"),
+            in_language("2", "pt-br"),
+            in_language("2", "es"),
+            in_language("3", "fr"),
+        ];
+        let ids: Vec<String> = merge_languages(chapters, &["en", "es"])
+            .into_iter()
+            .map(|c| c.id)
+            .collect();
+        assert_eq!(ids, ["1-en", "2-es", "3-fr"]);
+    }
+}
@@ -1,7 +1,15 @@
 use crate::chapter::ChapterClient;
-use crate::error::Result;
-use crate::image::ImageClient;
+use crate::error::{ApiErrorBody, Result, YomuError};
+use crate::hosts::HostAllowlist;
+use crate::image::{AT_HOME_TTL, AtHomeCache, DecodeLimits, ImageClient};
+use crate::manga::MangaClient;
+use crate::ratelimit::{Endpoint, RateLimiter, RateLimits};
+use crate::report::{DEFAULT_REPORT_URL, Reporter};
+use crate::retry::RetryPolicy;
 use crate::search::SearchClient;
+use crate::tag::TagClient;
+use reqwest::header::HeaderMap;
+use std::sync::Arc;
 use std::time::Duration;
 
 /// A client for interacting with the MangaDex API.
@@ -9,9 +17,21 @@ use std::time::Duration;
 /// This client serves as the entry point for searching manga,
 /// listing chapters, and fetching image data.
 ///
+/// Cloning is cheap: clones share the same connection pool and settings, so
+/// a clone can be moved into a spawned task. Sub-clients such as
+/// [`SearchClient`] own a clone too and are `Send + 'static`.
+///
+/// API requests from every clone share one rate limiter (see
+/// [`RateLimits`]), so a burst of activity waits instead of getting the IP
+/// banned. CDN downloads are not throttled by it. Transient failures of
+/// both are retried with backoff according to the [`RetryPolicy`].
+///
+/// Use [`MangaDexClient::builder`] to change the API URL, user agent,
+/// timeouts, proxy, TLS roots, rate limits or retry policy.
+///
 /// # Example
 ///
-/// ```rust
+/// ```rust,no_run
 /// use yomu::MangaDexClient;
 ///
 /// #[tokio::main]
@@ -22,20 +42,55 @@ use std::time::Duration;
 ///     Ok(())
 /// }
 /// ```
+#[derive(Clone)]
 pub struct MangaDexClient {
+    inner: Arc<ClientInner>,
+}
+
+/// State shared by every clone of a [`MangaDexClient`].
+struct ClientInner {
     http_client: reqwest::Client,
-    pub(crate) base_url: String,
+    base_url: String,
+    rate_limiter: RateLimiter,
+    retry_policy: RetryPolicy,
+    at_home_cache: AtHomeCache,
+    reporter: Option<Reporter>,
+    verify_hashes: bool,
+    decode_limits: DecodeLimits,
+    allowed_hosts: HostAllowlist,
 }
+
 impl MangaDexClient {
     /// Creates a new `MangaDexClient` with default settings.
     pub fn new() -> Result<Self> {
-        Ok(Self {
-            http_client: reqwest::Client::builder()
-                .user_agent("Yomu/0.1.0")
-                .timeout(Duration::from_secs(30))
-                .build()?,
-            base_url: "https://api.mangadex.org".into(),
-        })
+        Self::builder().build()
+    }
+
+    /// Returns a builder for a client with custom connection settings.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use yomu::MangaDexClient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = MangaDexClient::builder()
+    ///     .base_url("http://127.0.0.1:8080")
+    ///     .user_agent("my-reader/1.0")
+    ///     .connect_timeout(Duration::from_secs(5))
+    ///     .proxy(reqwest::Proxy::all("socks5h://127.0.0.1:1080")?)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn builder() -> MangaDexClientBuilder {
+        MangaDexClientBuilder::new()
+    }
+
+    /// Base URL of the MangaDex API, without a trailing slash.
+    pub(crate) fn base_url(&self) -> &str {
+        &self.inner.base_url
     }
 
     /// Returns a reference to the underlying HTTP client.
@@ -45,7 +100,7 @@ impl MangaDexClient {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,no_run
     /// use yomu::MangaDexClient;
     ///
     /// # #[tokio::main]
@@ -57,20 +112,446 @@ impl MangaDexClient {
     /// # }
     /// ```
     pub fn http_client(&self) -> &reqwest::Client {
-        &self.http_client
+        &self.inner.http_client
+    }
+
+    /// Cached @Home server assignments, shared by every clone.
+    pub(crate) fn at_home_cache(&self) -> &AtHomeCache {
+        &self.inner.at_home_cache
+    }
+
+    /// Sends @Home download reports, if they are enabled.
+    pub(crate) fn reporter(&self) -> Option<&Reporter> {
+        self.inner.reporter.as_ref()
+    }
+
+    /// Hosts images may be downloaded from.
+    pub fn allowed_hosts(&self) -> &HostAllowlist {
+        &self.inner.allowed_hosts
+    }
+
+    /// Limits applied when decoding downloaded images.
+    pub fn decode_limits(&self) -> &DecodeLimits {
+        &self.inner.decode_limits
+    }
+
+    /// Whether downloaded pages are checked against the hash in their filename.
+    pub(crate) fn verifies_hashes(&self) -> bool {
+        self.inner.verify_hashes
+    }
+
+    /// The policy used to retry failed requests.
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.inner.retry_policy
+    }
+
+    /// Sends an API request once the rate limiter allows it, feeds the
+    /// response's rate limit headers back into the limiter, and turns error
+    /// statuses into errors: [`YomuError::NotFound`] for `404`,
+    /// [`YomuError::RateLimited`] for `429` and [`YomuError::Api`], with the
+    /// errors MangaDex listed in the body, for the rest.
+    ///
+    /// Transient failures are retried according to the retry policy, each
+    /// attempt waiting for the rate limiter again.
+    pub(crate) async fn send(
+        &self,
+        endpoint: Endpoint,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response> {
+        self.inner
+            .retry_policy
+            .run(|| async {
+                // A streaming body cannot be cloned; send such a request once.
+                let request = match request.try_clone() {
+                    Some(request) => request,
+                    None => {
+                        return Err(YomuError::Io(std::io::Error::other(
+                            "request body is not replayable",
+                        )));
+                    }
+                };
+                self.inner.rate_limiter.acquire(endpoint).await;
+                let resp = request.send().await?;
+                self.inner.rate_limiter.observe(endpoint, resp.headers());
+                if resp.status().is_success() {
+                    Ok(resp)
+                } else {
+                    Err(api_error(resp).await)
+                }
+            })
+            .await
     }
 
     /// Returns a `SearchClient` for searching manga.
-    pub fn search_client<'mangaclient>(&'mangaclient self) -> SearchClient<'mangaclient> {
-        return SearchClient { client: self };
+    pub fn search_client(&self) -> SearchClient {
+        SearchClient {
+            client: self.clone(),
+            includes: Vec::new(),
+        }
+    }
+
+    /// Returns a `MangaClient` for fetching manga details and statistics.
+    pub fn manga_client(&self) -> MangaClient {
+        MangaClient {
+            client: self.clone(),
+            includes: Vec::new(),
+        }
     }
 
     /// Returns a `ChapterClient` for fetching chapter data.
-    pub fn chapter_client<'mangaclient>(&'mangaclient self) -> ChapterClient<'mangaclient> {
-        return ChapterClient { client: self };
+    pub fn chapter_client(&self) -> ChapterClient {
+        ChapterClient {
+            client: self.clone(),
+            includes: Vec::new(),
+            blocked_groups: Vec::new(),
+        }
     }
     /// Returns an `ImageClient` for fetching image data and URLs.
-    pub fn image_client<'mangaclient>(&'mangaclient self) -> ImageClient<'mangaclient> {
-        return ImageClient { client: self };
+    pub fn image_client(&self) -> ImageClient {
+        ImageClient {
+            client: self.clone(),
+        }
+    }
+
+    /// Returns a `TagClient` for fetching the tag catalogue.
+    pub fn tag_client(&self) -> TagClient {
+        TagClient {
+            client: self.clone(),
+        }
+    }
+}
+
+/// Default MangaDex API URL.
+pub const DEFAULT_BASE_URL: &str = "https://api.mangadex.org";
+/// Default `User-Agent` header sent with every request.
+pub const DEFAULT_USER_AGENT: &str = "Yomu/0.1.0";
+/// Default limit on the total time of a single request.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Builds the error for an unsuccessful API response.
+async fn api_error(resp: reqwest::Response) -> YomuError {
+    let status = resp.status();
+    match status {
+        reqwest::StatusCode::NOT_FOUND => YomuError::NotFound {
+            url: resp.url().to_string(),
+        },
+        reqwest::StatusCode::TOO_MANY_REQUESTS => YomuError::RateLimited {
+            retry_after: crate::ratelimit::retry_after(resp.headers()),
+        },
+        _ => YomuError::Api {
+            status,
+            errors: resp
+                .json::<ApiErrorBody>()
+                .await
+                .map(|body| body.errors)
+                .unwrap_or_default(),
+        },
+    }
+}
+
+/// Builds a [`MangaDexClient`] with custom connection settings.
+///
+/// Created by [`MangaDexClient::builder`]. Every setting starts at the value
+/// [`MangaDexClient::new`] uses.
+pub struct MangaDexClientBuilder {
+    base_url: String,
+    user_agent: String,
+    timeout: Duration,
+    connect_timeout: Option<Duration>,
+    proxy: Option<reqwest::Proxy>,
+    root_certificates: Vec<reqwest::Certificate>,
+    default_headers: HeaderMap,
+    rate_limits: RateLimits,
+    retry_policy: RetryPolicy,
+    at_home_ttl: Duration,
+    report_url: Option<String>,
+    verify_hashes: bool,
+    decode_limits: DecodeLimits,
+    allowed_hosts: HostAllowlist,
+}
+
+impl MangaDexClientBuilder {
+    /// Creates a builder with the default settings.
+    pub fn new() -> Self {
+        MangaDexClientBuilder {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            timeout: DEFAULT_TIMEOUT,
+            connect_timeout: None,
+            proxy: None,
+            root_certificates: Vec::new(),
+            default_headers: HeaderMap::new(),
+            rate_limits: RateLimits::default(),
+            retry_policy: RetryPolicy::default(),
+            at_home_ttl: AT_HOME_TTL,
+            report_url: None,
+            verify_hashes: true,
+            decode_limits: DecodeLimits::default(),
+            allowed_hosts: HostAllowlist::default(),
+        }
+    }
+
+    /// Sets the API URL, e.g. a local mock server. A trailing slash is ignored.
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with every request.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Sets the limit on the total time of a single request.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the limit on the time spent establishing a connection.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Routes every request through `proxy`. HTTP, HTTPS and SOCKS proxies
+    /// are supported.
+    ///
+    /// Without a proxy the client follows the `HTTP_PROXY`/`HTTPS_PROXY`
+    /// environment variables; setting one here overrides them.
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Trusts `certificate` as a root, in addition to the built-in roots.
+    pub fn add_root_certificate(mut self, certificate: reqwest::Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Sends `headers` with every request, replacing any earlier value of the
+    /// same header.
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers.extend(headers);
+        self
+    }
+
+    /// Sets the API request budgets. Raise them when talking to a mock server.
+    pub fn rate_limits(mut self, limits: RateLimits) -> Self {
+        self.rate_limits = limits;
+        self
+    }
+
+    /// Sets how failed API requests and CDN downloads are retried.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Sets how long @Home server assignments are reused before asking the
+    /// API again. `Duration::ZERO` disables the cache.
+    pub fn at_home_ttl(mut self, ttl: Duration) -> Self {
+        self.at_home_ttl = ttl;
+        self
+    }
+
+    /// Reports the outcome of every image download from an @Home node to
+    /// the MangaDex@Home network, which uses the reports to retire bad
+    /// nodes. Off by default.
+    ///
+    /// Reports are batched and sent in the background, and never slow down
+    /// or fail a download. Downloads from `uploads.mangadex.org` are not
+    /// reported.
+    pub fn at_home_reports(mut self, enabled: bool) -> Self {
+        self.report_url = enabled.then(|| DEFAULT_REPORT_URL.to_string());
+        self
+    }
+
+    /// Enables @Home reports and sends them to `url` instead of the MangaDex
+    /// endpoint, e.g. a local stand-in.
+    pub fn report_url(mut self, url: impl Into<String>) -> Self {
+        self.report_url = Some(url.into());
+        self
+    }
+
+    /// Checks every downloaded page against the SHA-256 MangaDex embeds in
+    /// its filename, failing with [`YomuError::Integrity`] on a mismatch.
+    /// On by default.
+    pub fn verify_hashes(mut self, enabled: bool) -> Self {
+        self.verify_hashes = enabled;
+        self
+    }
+
+    /// Sets the largest image dimensions and decoder allocation accepted
+    /// when decoding pages and covers.
+    pub fn decode_limits(mut self, limits: DecodeLimits) -> Self {
+        self.decode_limits = limits;
+        self
+    }
+
+    /// Sets the hosts images may be downloaded from, e.g. to add a mirror.
+    ///
+    /// @Home servers assigned by the API outside this list are rejected, and
+    /// redirects may only leave the current host for one on the list. No
+    /// redirect may go from HTTPS to HTTP.
+    pub fn allowed_hosts(mut self, hosts: HostAllowlist) -> Self {
+        self.allowed_hosts = hosts;
+        self
+    }
+
+    /// Builds the client.
+    ///
+    /// Fails if the user agent is not a valid header value or the TLS
+    /// backend rejects a root certificate.
+    pub fn build(self) -> Result<MangaDexClient> {
+        let mut http = reqwest::Client::builder()
+            .user_agent(self.user_agent)
+            .timeout(self.timeout)
+            .default_headers(self.default_headers)
+            .tls_certs_merge(self.root_certificates)
+            .redirect(self.allowed_hosts.redirect_policy());
+        if let Some(timeout) = self.connect_timeout {
+            http = http.connect_timeout(timeout);
+        }
+        if let Some(proxy) = self.proxy {
+            http = http.proxy(proxy);
+        }
+        let http_client = http.build()?;
+        let reporter = self
+            .report_url
+            .map(|url| Reporter::new(http_client.clone(), url));
+        Ok(MangaDexClient {
+            inner: Arc::new(ClientInner {
+                http_client,
+                base_url: self.base_url,
+                rate_limiter: RateLimiter::new(self.rate_limits),
+                retry_policy: self.retry_policy,
+                at_home_cache: AtHomeCache::new(self.at_home_ttl),
+                reporter,
+                verify_hashes: self.verify_hashes,
+                decode_limits: self.decode_limits,
+                allowed_hosts: self.allowed_hosts,
+            }),
+        })
+    }
+}
+
+impl Default for MangaDexClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::serve_once;
+    use tokio::net::TcpListener;
+
+    /// Serves one canned HTTP response on a local port and returns the error
+    /// `fetch_manga` reports for it.
+    async fn fetch_manga_error(response: String) -> YomuError {
+        let (base_url, _server) = serve_once(response).await;
+        let client = MangaDexClient::builder()
+            .base_url(base_url)
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .unwrap();
+        match client.manga_client().fetch_manga("x").await {
+            Ok(_) => panic!("expected an error"),
+            Err(e) => e,
+        }
+    }
+
+    #[tokio::test]
+    async fn error_responses_map_to_typed_errors() {
+        let body = r#"{"result":"error","errors":[{"id":"abc","status":400,"title":"Bad Request","detail":"Invalid manga id","context":null}]}"#;
+        let error = fetch_manga_error(format!(
+            "HTTP/1.1 400 Bad Request\r\ncontent-length: {}\r\n\r\n{body}",
+            body.len()
+        ))
+        .await;
+        match error {
+            YomuError::Api { status, errors } => {
+                assert_eq!(status, reqwest::StatusCode::BAD_REQUEST);
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].status, 400);
+                assert_eq!(errors[0].detail.as_deref(), Some("Invalid manga id"));
+            }
+            other => panic!("expected an API error, got {other}"),
+        }
+
+        let error =
+            fetch_manga_error("HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\n\r\n".to_string())
+                .await;
+        assert!(matches!(error, YomuError::NotFound { url } if url.contains("/manga/x")));
+
+        let error = fetch_manga_error(
+            "HTTP/1.1 429 Too Many Requests\r\nretry-after: 7\r\ncontent-length: 0\r\n\r\n"
+                .to_string(),
+        )
+        .await;
+        assert!(error.is_retryable());
+        assert!(matches!(
+            error,
+            YomuError::RateLimited {
+                retry_after: Some(delay)
+            } if delay == Duration::from_secs(7)
+        ));
+    }
+
+    #[tokio::test]
+    async fn stalled_responses_time_out() {
+        // Connections are accepted by the OS but never answered.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = MangaDexClient::builder()
+            .base_url(format!("http://{}", listener.local_addr().unwrap()))
+            .timeout(Duration::from_millis(200))
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .unwrap();
+        let error = match client.manga_client().fetch_manga("x").await {
+            Ok(_) => panic!("expected an error"),
+            Err(e) => e,
+        };
+        assert!(error.is_retryable());
+        assert!(matches!(
+            error,
+            YomuError::Timeout { url: Some(url) } if url.contains("/manga/x")
+        ));
+    }
+
+    fn assert_send_static<T: Send + 'static>(_: T) {}
+
+    #[test]
+    fn sub_clients_can_move_into_tasks() {
+        let client = MangaDexClient::new().unwrap();
+        assert_send_static(client.clone());
+        assert_send_static(client.search_client());
+        assert_send_static(client.manga_client());
+        assert_send_static(client.chapter_client());
+        assert_send_static(client.image_client());
+        assert_send_static(client.tag_client());
+    }
+
+    #[test]
+    fn builder_overrides_connection_settings() {
+        let client = MangaDexClient::builder()
+            .base_url("http://127.0.0.1:8080/")
+            .user_agent("team-reader/2.0")
+            .connect_timeout(Duration::from_secs(1))
+            .proxy(reqwest::Proxy::all("socks5h://127.0.0.1:1080").unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(client.base_url(), "http://127.0.0.1:8080");
+        assert_eq!(MangaDexClient::new().unwrap().base_url(), DEFAULT_BASE_URL);
+        assert!(
+            MangaDexClient::builder()
+                .user_agent("bad\nagent")
+                .build()
+                .is_err()
+        );
     }
 }
@@ -1,4 +1,6 @@
+use serde::Deserialize;
 use std::fmt;
+use std::time::Duration;
 
 /// A type alias for `Result<T, YomuError>`.
 pub type Result<T> = std::result::Result<T, YomuError>;
@@ -6,12 +8,134 @@ pub type Result<T> = std::result::Result<T, YomuError>;
 /// The error type for all yomu operations.
 #[derive(Debug)]
 pub enum YomuError {
-    /// An HTTP request error from reqwest.
+    /// An HTTP request error from reqwest that no other variant describes,
+    /// e.g. a refused connection or an error status from an image server.
     Http(reqwest::Error),
     /// An I/O error.
     Io(std::io::Error),
-    /// An image decoding/encoding error.
-    Image(image::ImageError),
+    /// The MangaDex API answered with an error status.
+    Api {
+        /// The response status.
+        status: reqwest::StatusCode,
+        /// The errors MangaDex listed in the response body, if it sent any.
+        errors: Vec<ApiError>,
+    },
+    /// A server answered `429 Too Many Requests`.
+    RateLimited {
+        /// How long the server asked the client to wait, if it said.
+        retry_after: Option<Duration>,
+    },
+    /// The requested resource does not exist.
+    NotFound {
+        /// The URL that was requested.
+        url: String,
+    },
+    /// A URL was refused because it is not HTTPS on an allowed host (see
+    /// [`HostAllowlist`](crate::hosts::HostAllowlist)).
+    UnsafeUrl {
+        /// The refused URL.
+        url: String,
+    },
+    /// A download or image was larger than the client allows.
+    SizeLimit(SizeLimit),
+    /// An image could not be decoded.
+    Decode(image::ImageError),
+    /// A request timed out.
+    Timeout {
+        /// The URL that was requested, if known.
+        url: Option<String>,
+    },
+    /// A downloaded page did not match the SHA-256 in its filename, e.g.
+    /// because an @Home node sent a truncated or corrupted file.
+    Integrity {
+        /// The page URL.
+        url: String,
+        /// Hash from the filename, as lowercase hex.
+        expected: String,
+        /// Hash of the bytes received, as lowercase hex.
+        actual: String,
+    },
+}
+
+/// One entry of the `errors` array in a MangaDex error response.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct ApiError {
+    /// Identifier of this occurrence, useful when reporting problems to MangaDex.
+    pub id: String,
+    /// The HTTP status this error maps to.
+    pub status: u16,
+    /// A short summary, e.g. "Validation exception".
+    pub title: String,
+    /// A longer, human-readable explanation.
+    pub detail: Option<String>,
+    /// Extra information about where the error occurred.
+    pub context: Option<String>,
+}
+
+/// The body of a MangaDex error response.
+#[derive(Debug, Deserialize)]
+pub(crate) struct ApiErrorBody {
+    #[serde(default)]
+    pub(crate) errors: Vec<ApiError>,
+}
+
+/// The limit exceeded in a [`YomuError::SizeLimit`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SizeLimit {
+    /// A response body was larger than the client accepts.
+    Body {
+        /// The URL that was downloaded.
+        url: String,
+        /// The most bytes accepted.
+        max_bytes: u64,
+    },
+    /// An image's dimensions exceed the
+    /// [`DecodeLimits`](crate::image::DecodeLimits).
+    Dimensions {
+        /// Width from the image header, in pixels.
+        width: u32,
+        /// Height from the image header, in pixels.
+        height: u32,
+    },
+    /// Decoding an image would allocate more memory than the
+    /// [`DecodeLimits`](crate::image::DecodeLimits) allow.
+    Allocation {
+        /// Width from the image header, in pixels.
+        width: u32,
+        /// Height from the image header, in pixels.
+        height: u32,
+    },
+}
+
+impl YomuError {
+    /// Returns `true` if the failure is likely transient and the request is
+    /// worth repeating: timeouts, dropped connections, rate limiting, and
+    /// `408` or `5xx` responses.
+    pub fn is_retryable(&self) -> bool {
+        let retryable_status = |status: reqwest::StatusCode| {
+            status == reqwest::StatusCode::REQUEST_TIMEOUT || status.is_server_error()
+        };
+        match self {
+            YomuError::Http(e) => match e.status() {
+                Some(status) => retryable_status(status),
+                None => e.is_connect() || e.is_request() || e.is_body(),
+            },
+            YomuError::Io(e) => matches!(
+                e.kind(),
+                std::io::ErrorKind::TimedOut
+                    | std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::UnexpectedEof
+            ),
+            YomuError::Api { status, .. } => retryable_status(*status),
+            YomuError::RateLimited { .. } | YomuError::Timeout { .. } => true,
+            YomuError::NotFound { .. } | YomuError::UnsafeUrl { .. } => false,
+            // The client does not retry these. The reader fails over to another
+            // node on `Integrity` only; oversized or undecodable pages fail.
+            YomuError::SizeLimit(_) | YomuError::Decode(_) | YomuError::Integrity { .. } => false,
+        }
+    }
 }
 
 impl fmt::Display for YomuError {
@@ -19,7 +143,58 @@ impl fmt::Display for YomuError {
         match self {
             YomuError::Http(e) => write!(f, "HTTP error: {e}"),
             YomuError::Io(e) => write!(f, "IO error: {e}"),
-            YomuError::Image(e) => write!(f, "Image error: {e}"),
+            YomuError::Api { status, errors } => {
+                write!(f, "API error: {status}")?;
+                for error in errors {
+                    write!(f, "; {error}")?;
+                }
+                Ok(())
+            }
+            YomuError::RateLimited {
+                retry_after: Some(delay),
+            } => write!(f, "Rate limited: retry after {}s", delay.as_secs()),
+            YomuError::RateLimited { retry_after: None } => write!(f, "Rate limited"),
+            YomuError::NotFound { url } => write!(f, "Not found: {url}"),
+            YomuError::UnsafeUrl { url } => {
+                write!(f, "Unsafe URL: {url} is not HTTPS on an allowed host")
+            }
+            YomuError::SizeLimit(limit) => write!(f, "Size limit exceeded: {limit}"),
+            YomuError::Decode(e) => write!(f, "Decode error: {e}"),
+            YomuError::Timeout { url: Some(url) } => write!(f, "Timed out: {url}"),
+            YomuError::Timeout { url: None } => write!(f, "Timed out"),
+            YomuError::Integrity {
+                url,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Integrity error: {url} has SHA-256 {actual}, expected {expected}"
+            ),
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.detail {
+            Some(detail) => write!(f, "{}: {detail}", self.title),
+            None => write!(f, "{}", self.title),
+        }
+    }
+}
+
+impl fmt::Display for SizeLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SizeLimit::Body { url, max_bytes } => {
+                write!(f, "{url} is larger than {max_bytes} bytes")
+            }
+            SizeLimit::Dimensions { width, height } => {
+                write!(f, "{width}x{height} image is too large to decode")
+            }
+            SizeLimit::Allocation { width, height } => {
+                write!(f, "{width}x{height} image needs too much memory to decode")
+            }
         }
     }
 }
@@ -29,14 +204,29 @@ impl std::error::Error for YomuError {
         match self {
             YomuError::Http(e) => Some(e),
             YomuError::Io(e) => Some(e),
-            YomuError::Image(e) => Some(e),
+            YomuError::Decode(e) => Some(e),
+            _ => None,
         }
     }
 }
 
 impl From<reqwest::Error> for YomuError {
+    /// Timeouts, `404` and `429` statuses get their own variants; everything
+    /// else stays [`YomuError::Http`].
     fn from(err: reqwest::Error) -> Self {
-        YomuError::Http(err)
+        let url = err.url().map(|url| url.to_string());
+        if err.is_timeout() {
+            return YomuError::Timeout { url };
+        }
+        match err.status() {
+            Some(reqwest::StatusCode::NOT_FOUND) => YomuError::NotFound {
+                url: url.unwrap_or_default(),
+            },
+            Some(reqwest::StatusCode::TOO_MANY_REQUESTS) => {
+                YomuError::RateLimited { retry_after: None }
+            }
+            _ => YomuError::Http(err),
+        }
     }
 }
 
@@ -48,6 +238,6 @@ impl From<std::io::Error> for YomuError {
 
 impl From<image::ImageError> for YomuError {
     fn from(err: image::ImageError) -> Self {
-        YomuError::Image(err)
+        YomuError::Decode(err)
     }
 }
@@ -0,0 +1,207 @@
+use yomu::query::{
+    ContentRating, Demographic, MangaStatus, OrderField, SearchQuery, SortDirection,
+};
+
+/// Original languages offered in the filter panel.
+pub const ORIGINAL_LANGUAGES: [&str; 5] = ["ja", "ko", "zh", "zh-hk", "en"];
+
+/// A row in the Search screen's filter panel.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FilterRow {
+    Status,
+    Demographic,
+    ContentRating,
+    Year,
+    OriginalLanguage,
+    SortBy,
+    /// Opens the tag browser rather than cycling a value.
+    Tags,
+}
+
+impl FilterRow {
+    /// Every row, top to bottom.
+    pub const ALL: [FilterRow; 7] = [
+        FilterRow::Status,
+        FilterRow::Demographic,
+        FilterRow::ContentRating,
+        FilterRow::Year,
+        FilterRow::OriginalLanguage,
+        FilterRow::SortBy,
+        FilterRow::Tags,
+    ];
+
+    /// The label shown to the left of the row's value.
+    pub fn label(&self) -> &'static str {
+        match self {
+            FilterRow::Status => "Status",
+            FilterRow::Demographic => "Demographic",
+            FilterRow::ContentRating => "Rating",
+            FilterRow::Year => "Year",
+            FilterRow::OriginalLanguage => "Language",
+            FilterRow::SortBy => "Sort by",
+            FilterRow::Tags => "Tags",
+        }
+    }
+
+    /// The row's current value in `query`, or "Any" when unset.
+    pub fn value(&self, query: &SearchQuery) -> String {
+        let value = match self {
+            FilterRow::Status => query.status.first().map(|s| s.as_str().to_string()),
+            FilterRow::Demographic => query
+                .publication_demographic
+                .first()
+                .map(|d| d.as_str().to_string()),
+            FilterRow::ContentRating => {
+                query.content_rating.first().map(|r| r.as_str().to_string())
+            }
+            FilterRow::Year => query.year.map(|y| y.to_string()),
+            FilterRow::OriginalLanguage => query.original_language.first().cloned(),
+            FilterRow::SortBy => query.order.first().map(|(f, _)| f.as_str().to_string()),
+            FilterRow::Tags => {
+                let (included, excluded) = (query.included_tags.len(), query.excluded_tags.len());
+                (included + excluded > 0).then(|| format!("+{included} -{excluded}"))
+            }
+        };
+        value.unwrap_or_else(|| "Any".to_string())
+    }
+
+    /// Steps the row's value forwards or backwards, wrapping through "Any".
+    pub fn cycle(&self, query: &mut SearchQuery, forward: bool) {
+        match self {
+            FilterRow::Status => cycle_single(&mut query.status, &MangaStatus::ALL, forward),
+            FilterRow::Demographic => cycle_single(
+                &mut query.publication_demographic,
+                &Demographic::ALL,
+                forward,
+            ),
+            FilterRow::ContentRating => {
+                cycle_single(&mut query.content_rating, &ContentRating::ALL, forward)
+            }
+            FilterRow::Year => query.year = step_year(query.year, forward, current_year()),
+            FilterRow::OriginalLanguage => {
+                let languages: Vec<String> =
+                    ORIGINAL_LANGUAGES.iter().map(|l| l.to_string()).collect();
+                cycle_single(&mut query.original_language, &languages, forward);
+            }
+            FilterRow::SortBy => {
+                let mut fields: Vec<OrderField> = query.order.iter().map(|(f, _)| *f).collect();
+                cycle_single(&mut fields, &OrderField::ALL, forward);
+                query.order = fields
+                    .into_iter()
+                    .map(|field| {
+                        let direction = match field {
+                            OrderField::Title => SortDirection::Ascending,
+                            _ => SortDirection::Descending,
+                        };
+                        (field, direction)
+                    })
+                    .collect();
+            }
+            FilterRow::Tags => {}
+        }
+    }
+}
+
+/// Steps a year filter forwards or backwards. An unset year starts from
+/// `current`, and stepping back from year zero returns to "Any".
+fn step_year(year: Option<usize>, forward: bool, current: usize) -> Option<usize> {
+    match year {
+        None => Some(current),
+        Some(year) if forward => Some(year + 1),
+        Some(year) => year.checked_sub(1),
+    }
+}
+
+/// Appends a typed digit to a year filter; input stops at four digits.
+pub fn push_year_digit(year: Option<usize>, digit: usize) -> Option<usize> {
+    match year.unwrap_or(0) {
+        year if year < 1000 => Some(year * 10 + digit),
+        _ => year,
+    }
+}
+
+/// Removes the last digit of a year filter, returning to "Any" when none
+/// are left.
+pub fn pop_year_digit(year: Option<usize>) -> Option<usize> {
+    year.map(|y| y / 10).filter(|&y| y > 0)
+}
+
+/// The current calendar year (UTC).
+fn current_year() -> usize {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    year_of_day(secs / 86_400)
+}
+
+/// The Gregorian year of a day counted from 1970-01-01, using Howard
+/// Hinnant's `civil_from_days` algorithm.
+fn year_of_day(days: u64) -> usize {
+    // Shift the epoch to 0000-03-01 so leap days fall at the end of a year.
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    // Months count from March, so January and February belong to the next year.
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let year = era * 400 + year_of_era + u64::from(month_from_march >= 10);
+    year as usize
+}
+
+/// Replaces `values` with the next (or previous) single entry of `all`.
+///
+/// An empty list stands for "Any" and sits before the first entry.
+fn cycle_single<T: Clone + PartialEq>(values: &mut Vec<T>, all: &[T], forward: bool) {
+    // Position 0 is "Any"; positions 1..=all.len() are the entries of `all`.
+    let current = values
+        .first()
+        .and_then(|v| all.iter().position(|a| a == v))
+        .map_or(0, |i| i + 1);
+    let len = all.len() + 1;
+    let next = if forward {
+        (current + 1) % len
+    } else {
+        (current + len - 1) % len
+    };
+    values.clear();
+    if next > 0 {
+        values.push(all[next - 1].clone());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn year_of_day_changes_exactly_at_new_year() {
+        assert_eq!(year_of_day(0), 1970);
+        // 2000-02-29 and 2000-12-31, across a leap day.
+        assert_eq!(year_of_day(11_016), 2000);
+        assert_eq!(year_of_day(11_322), 2000);
+        // 2025-12-31 and 2026-01-01.
+        assert_eq!(year_of_day(20_453), 2025);
+        assert_eq!(year_of_day(20_454), 2026);
+    }
+
+    #[test]
+    fn year_steps_from_the_current_year_and_back_to_any() {
+        assert_eq!(step_year(None, true, 2026), Some(2026));
+        assert_eq!(step_year(None, false, 2026), Some(2026));
+        assert_eq!(step_year(Some(2026), true, 2026), Some(2027));
+        assert_eq!(step_year(Some(1999), false, 2026), Some(1998));
+        assert_eq!(step_year(Some(0), false, 2026), None);
+    }
+
+    #[test]
+    fn year_digits_are_typed_and_erased() {
+        let typed = [2, 0, 1, 9, 5].into_iter().fold(None, push_year_digit);
+        // The fifth digit is ignored.
+        assert_eq!(typed, Some(2019));
+        assert_eq!(pop_year_digit(typed), Some(201));
+        assert_eq!(pop_year_digit(Some(2)), None);
+        assert_eq!(pop_year_digit(None), None);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::error::{Result, YomuError};
+use reqwest::Url;
+use reqwest::redirect::{Attempt, Policy};
+
+/// Redirect chains longer than this are refused (reqwest's default).
+const MAX_REDIRECTS: usize = 10;
+
+/// The hosts images may be downloaded from.
+///
+/// An entry is either an exact host name (`uploads.mangadex.org`) or a
+/// wildcard (`*.mangadex.network`) matching any subdomain, but not the
+/// domain itself. The default allows MangaDex@Home nodes and MangaDex's own
+/// image server; add entries to use a mirror:
+///
+/// ```rust,no_run
+/// use yomu::{HostAllowlist, MangaDexClient};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = MangaDexClient::builder()
+///     .allowed_hosts(HostAllowlist::default().allow("images.mirror.example"))
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Set with [`MangaDexClientBuilder::allowed_hosts`](crate::client::MangaDexClientBuilder::allowed_hosts).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostAllowlist {
+    hosts: Vec<String>,
+}
+
+impl HostAllowlist {
+    /// An allowlist that allows nothing.
+    pub fn empty() -> Self {
+        HostAllowlist { hosts: Vec::new() }
+    }
+
+    /// Adds a host name or `*.` wildcard.
+    pub fn allow(mut self, host: impl Into<String>) -> Self {
+        self.hosts.push(host.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Returns `true` if `host` matches an entry.
+    pub fn allows_host(&self, host: &str) -> bool {
+        let host = host.to_ascii_lowercase();
+        self.hosts
+            .iter()
+            .any(|entry| match entry.strip_prefix("*.") {
+                Some(domain) => host
+                    .strip_suffix(domain)
+                    .is_some_and(|sub| sub.len() > 1 && sub.ends_with('.')),
+                None => host == *entry,
+            })
+    }
+
+    /// Returns `true` if `url` uses HTTPS and its host is allowed.
+    pub fn allows(&self, url: &Url) -> bool {
+        url.scheme() == "https" && url.host_str().is_some_and(|host| self.allows_host(host))
+    }
+
+    /// Fails unless `url` is an HTTPS URL on an allowed host.
+    pub(crate) fn check(&self, url: &str) -> Result<()> {
+        match Url::parse(url) {
+            Ok(parsed) if self.allows(&parsed) => Ok(()),
+            _ => Err(YomuError::UnsafeUrl {
+                url: url.to_string(),
+            }),
+        }
+    }
+
+    /// A redirect policy that never downgrades from HTTPS to HTTP and only
+    /// leaves the current host for an allowed one.
+    pub(crate) fn redirect_policy(&self) -> Policy {
+        let allowlist = self.clone();
+        Policy::custom(move |attempt: Attempt| {
+            let Some(from) = attempt.previous().last() else {
+                return attempt.follow();
+            };
+            if attempt.previous().len() > MAX_REDIRECTS {
+                attempt.error("too many redirects")
+            } else if allowlist.allows_redirect(from, attempt.url()) {
+                attempt.follow()
+            } else {
+                let error = format!("refused redirect from {from} to {}", attempt.url());
+                attempt.error(error)
+            }
+        })
+    }
+
+    fn allows_redirect(&self, from: &Url, to: &Url) -> bool {
+        if from.scheme() == "https" && to.scheme() != "https" {
+            return false;
+        }
+        let same_host = from.host_str() == to.host_str()
+            && from.port_or_known_default() == to.port_or_known_default();
+        same_host || self.allows(to)
+    }
+}
+
+impl Default for HostAllowlist {
+    /// MangaDex@Home nodes and `uploads.mangadex.org`.
+    fn default() -> Self {
+        HostAllowlist::empty()
+            .allow("*.mangadex.network")
+            .allow("uploads.mangadex.org")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn url(s: &str) -> Url {
+        Url::parse(s).unwrap()
+    }
+
+    #[test]
+    fn default_allowlist_matches_mangadex_hosts() {
+        let hosts = HostAllowlist::default();
+        assert!(hosts.allows(&url("https://abc123.xyz.mangadex.network:443/data/h/1.png")));
+        assert!(hosts.allows(&url("https://uploads.mangadex.org/covers/x/c.jpg")));
+        assert!(!hosts.allows(&url("http://abc123.mangadex.network/data/h/1.png")));
+        assert!(!hosts.allows(&url("https://mangadex.network/data/h/1.png")));
+        assert!(!hosts.allows(&url("https://evilmangadex.network/data/h/1.png")));
+        assert!(!hosts.allows(&url("https://uploads.mangadex.org.evil.example/c.jpg")));
+        assert!(matches!(
+            hosts.check("https://example.com/1.png"),
+            Err(YomuError::UnsafeUrl { .. })
+        ));
+        assert!(
+            hosts
+                .clone()
+                .allow("Example.com")
+                .check("https://example.com/1.png")
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn redirects_stay_on_https_and_allowed_hosts() {
+        let hosts = HostAllowlist::default();
+        let node = url("https://a.mangadex.network/data/h/1.png");
+        assert!(hosts.allows_redirect(&node, &url("https://uploads.mangadex.org/data/h/1.png")));
+        assert!(!hosts.allows_redirect(&node, &url("http://a.mangadex.network/data/h/1.png")));
+        assert!(!hosts.allows_redirect(&node, &url("https://example.com/1.png")));
+
+        let api = url("https://api.mangadex.org/manga");
+        assert!(hosts.allows_redirect(&api, &url("https://api.mangadex.org/manga/")));
+        assert!(!hosts.allows_redirect(&api, &url("https://example.com/manga")));
+        let mock = url("http://127.0.0.1:8080/manga");
+        assert!(hosts.allows_redirect(&mock, &url("http://127.0.0.1:8080/manga/")));
+        assert!(!hosts.allows_redirect(&mock, &url("http://127.0.0.1:9090/manga")));
+    }
+}
@@ -1,22 +1,41 @@
 use crate::client::MangaDexClient;
-use crate::error::{Result, YomuError};
-use image::DynamicImage;
+use crate::error::{Result, SizeLimit, YomuError};
+use crate::ratelimit::Endpoint;
+use crate::report::Report;
+use image::{DynamicImage, ImageError, ImageReader, Limits};
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 /// Maximum bytes accepted for a single image download (50 MB).
 const MAX_IMAGE_BYTES: u64 = 50 * 1024 * 1024;
 
+/// MangaDex's own image server, used when no @Home node is reachable.
+pub const UPLOADS_BASE_URL: &str = "https://uploads.mangadex.org";
+
+/// How long an @Home server assignment stays valid. MangaDex documents
+/// 15 minutes.
+pub const AT_HOME_TTL: Duration = Duration::from_secs(15 * 60);
+
 /// A client for fetching image-related data from MangaDex @ Home servers.
-pub struct ImageClient<'mangaclient> {
-    /// Reference to the parent `MangaDexClient`.
-    pub client: &'mangaclient MangaDexClient,
+#[derive(Clone)]
+pub struct ImageClient {
+    /// The parent `MangaDexClient` (a cheap clone sharing its connection pool).
+    pub client: MangaDexClient,
 }
-impl<'mangaclient> ImageClient<'mangaclient> {
+impl ImageClient {
     /// Fetches image filenames and server information for a specific chapter ID.
     ///
+    /// Assignments are cached for the client's @Home TTL (see
+    /// [`AT_HOME_TTL`]), so reopening a chapter does not spend the 40 per
+    /// minute `/at-home/server` budget.
+    ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,no_run
     /// # use yomu::MangaDexClient;
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -27,62 +46,208 @@ impl<'mangaclient> ImageClient<'mangaclient> {
     /// # }
     /// ```
     pub async fn fetch_image_data(&self, chapter_id: &str) -> Result<ImageDataResponse> {
-        let fetch_url = format!("{}/at-home/server/{}", self.client.base_url, chapter_id);
-        let resp: reqwest::Response = self
-            .client
-            .http_client()
-            .get(fetch_url)
-            .send()
-            .await?
-            .error_for_status()?;
-        let resp_json = resp.json::<ImageDataResponse>().await?;
-        if !resp_json.base_url.starts_with("https://") {
-            return Err(YomuError::Io(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                format!(
-                    "unsafe base_url from API: expected https://, got \"{}\"",
-                    resp_json.base_url
-                ),
-            )));
+        if let Some(cached) = self.client.at_home_cache().get(chapter_id) {
+            return Ok(cached);
         }
+        self.refresh_image_data(chapter_id).await
+    }
+
+    /// Like [`fetch_image_data`](Self::fetch_image_data), but always asks
+    /// the API for a new assignment, e.g. because the cached node stopped
+    /// responding.
+    pub async fn refresh_image_data(&self, chapter_id: &str) -> Result<ImageDataResponse> {
+        let fetch_url = format!("{}/at-home/server/{}", self.client.base_url(), chapter_id);
+        let request = self.client.http_client().get(fetch_url);
+        let resp = self.client.send(Endpoint::AtHome, request).await?;
+        let resp_json = resp.json::<ImageDataResponse>().await?;
+        self.client.allowed_hosts().check(&resp_json.base_url)?;
+        self.client
+            .at_home_cache()
+            .insert(chapter_id, resp_json.clone());
         Ok(resp_json)
     }
     /// Downloads an image from the given URL and returns the raw bytes.
     ///
+    /// The URL must be HTTPS on one of the client's
+    /// [allowed hosts](crate::hosts::HostAllowlist). Transient failures are
+    /// retried according to the client's
+    /// [`RetryPolicy`](crate::retry::RetryPolicy).
+    ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,no_run
     /// # use yomu::MangaDexClient;
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// # let client = MangaDexClient::new()?;
-    /// let url = "https://example.com/image.jpg";
+    /// let url = "https://uploads.mangadex.org/covers/a96676e5-8ae2-425e-b549-7f15dd34a6d8/cover.jpg";
     /// let bytes = client.image_client().download_image_bytes(url).await?;
     /// # Ok(())
     /// # }
     /// ```
     pub async fn download_image_bytes(&self, url: &str) -> Result<Vec<u8>> {
-        let resp = self.client.http_client().get(url).send().await?;
-        if resp.content_length().map_or(false, |len| len > MAX_IMAGE_BYTES) {
-            return Err(YomuError::Io(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                "image response exceeds size limit",
-            )));
+        Ok(self.download_timed(url).await?.0)
+    }
+
+    /// Like [`download_image_bytes`](Self::download_image_bytes), but also
+    /// returns when the successful attempt started, so retry backoff is not
+    /// counted as transfer time.
+    pub(crate) async fn download_timed(&self, url: &str) -> Result<(Vec<u8>, Instant)> {
+        self.client.allowed_hosts().check(url)?;
+        self.client
+            .retry_policy()
+            .run(|| self.download_once(url))
+            .await
+    }
+
+    /// Downloads `url` once, checks it against the hash in its filename if
+    /// verification is enabled, and reports the outcome to the @Home network
+    /// if reporting is enabled. Returns the bytes and when the attempt started.
+    async fn download_once(&self, url: &str) -> Result<(Vec<u8>, Instant)> {
+        let started = Instant::now();
+        let result = self.fetch_bytes(url).await.and_then(|(bytes, cached)| {
+            if self.client.verifies_hashes() {
+                verify_page_hash(url, &bytes)?;
+            }
+            Ok((bytes, cached))
+        });
+        if let Some(reporter) = self.client.reporter() {
+            reporter.report(Report {
+                url: url.to_string(),
+                success: result.is_ok(),
+                cached: result.as_ref().is_ok_and(|(_, cached)| *cached),
+                bytes: result.as_ref().map_or(0, |(bytes, _)| bytes.len()),
+                duration: started.elapsed().as_millis() as u64,
+            });
+        }
+        result.map(|(bytes, _)| (bytes, started))
+    }
+
+    /// Downloads `url`, rejecting error statuses and oversized responses.
+    /// Also returns whether the server answered from its cache.
+    async fn fetch_bytes(&self, url: &str) -> Result<(Vec<u8>, bool)> {
+        let resp = self
+            .client
+            .http_client()
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?;
+        let cached = resp
+            .headers()
+            .get("x-cache")
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("HIT"));
+        if resp
+            .content_length()
+            .is_some_and(|len| len > MAX_IMAGE_BYTES)
+        {
+            return Err(YomuError::SizeLimit(SizeLimit::Body {
+                url: url.to_string(),
+                max_bytes: MAX_IMAGE_BYTES,
+            }));
         }
         let bytes = resp.bytes().await?;
         if bytes.len() as u64 > MAX_IMAGE_BYTES {
-            return Err(YomuError::Io(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                "image response exceeds size limit",
-            )));
+            return Err(YomuError::SizeLimit(SizeLimit::Body {
+                url: url.to_string(),
+                max_bytes: MAX_IMAGE_BYTES,
+            }));
         }
-        Ok(bytes.into())
+        Ok((bytes.into(), cached))
     }
     /// Downloads an image from the given URL and decodes it into a `DynamicImage`.
     pub async fn download_image(&self, url: &str) -> Result<DynamicImage> {
         let bytes: Vec<u8> = self.download_image_bytes(url).await?;
-        let img = image::load_from_memory(&bytes)?;
-        Ok(img)
+        self.decode_image(&bytes)
+    }
+
+    /// Decodes image bytes within the client's [`DecodeLimits`].
+    pub fn decode_image(&self, bytes: &[u8]) -> Result<DynamicImage> {
+        self.client.decode_limits().decode(bytes)
+    }
+}
+
+/// Limits on decoding an image, so a small file cannot expand into a huge
+/// bitmap (a "decompression bomb").
+///
+/// Set with [`MangaDexClientBuilder::decode_limits`](crate::client::MangaDexClientBuilder::decode_limits).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeLimits {
+    /// Widest image accepted, in pixels.
+    pub max_width: u32,
+    /// Tallest image accepted, in pixels. Long-strip chapters need a lot.
+    pub max_height: u32,
+    /// Most memory the decoder may allocate, in bytes.
+    pub max_alloc: u64,
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        DecodeLimits {
+            max_width: 16_384,
+            max_height: 32_768,
+            max_alloc: 256 * 1024 * 1024,
+        }
+    }
+}
+
+impl DecodeLimits {
+    /// Decodes `bytes`, guessing the format from its contents.
+    ///
+    /// Fails with [`YomuError::SizeLimit`] if the image is larger than
+    /// these limits allow, checking the dimensions in the header before any
+    /// pixels are decoded.
+    pub fn decode(&self, bytes: &[u8]) -> Result<DynamicImage> {
+        let (width, height) = ImageReader::new(Cursor::new(bytes))
+            .with_guessed_format()?
+            .into_dimensions()?;
+        if width > self.max_width || height > self.max_height {
+            return Err(YomuError::SizeLimit(SizeLimit::Dimensions {
+                width,
+                height,
+            }));
+        }
+        let mut limits = Limits::default();
+        limits.max_image_width = Some(self.max_width);
+        limits.max_image_height = Some(self.max_height);
+        limits.max_alloc = Some(self.max_alloc);
+        let mut reader = ImageReader::new(Cursor::new(bytes)).with_guessed_format()?;
+        reader.limits(limits);
+        reader.decode().map_err(|e| match e {
+            ImageError::Limits(_) => YomuError::SizeLimit(SizeLimit::Allocation { width, height }),
+            e => YomuError::Decode(e),
+        })
+    }
+}
+
+/// The SHA-256 MangaDex embeds in page filenames such as `A1-<hash>.jpg`,
+/// as lowercase hex. Accepts a bare filename or a full URL.
+pub fn filename_sha256(file: &str) -> Option<&str> {
+    let name = file.rsplit('/').next()?;
+    let stem = name.split('.').next()?;
+    let hash = stem.rsplit('-').next()?;
+    (hash.len() == 64 && hash.bytes().all(|b| b.is_ascii_hexdigit())).then_some(hash)
+}
+
+/// Checks `bytes` against the hash in the filename of `url`. URLs without
+/// one, such as cover art, always pass.
+pub(crate) fn verify_page_hash(url: &str, bytes: &[u8]) -> Result<()> {
+    let Some(expected) = filename_sha256(url) else {
+        return Ok(());
+    };
+    let actual: String = Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+    if actual.eq_ignore_ascii_case(expected) {
+        Ok(())
+    } else {
+        Err(YomuError::Integrity {
+            url: url.to_string(),
+            expected: expected.to_ascii_lowercase(),
+            actual,
+        })
     }
 }
 
@@ -97,6 +262,41 @@ pub struct ImageDataResponse {
     /// Image data and hash for the chapter.
     pub chapter: ImageAttributes,
 }
+impl ImageDataResponse {
+    /// URL of the page at `index` on the assigned server, or `None` if out
+    /// of range.
+    pub fn page_url(&self, index: usize, quality: ImageQuality) -> Option<String> {
+        let (quality, file) = self.chapter.page_file(index, quality)?;
+        Some(format!(
+            "{}/{}/{}/{}",
+            self.base_url,
+            quality.path(),
+            self.chapter.hash,
+            file
+        ))
+    }
+}
+
+/// Which version of a chapter's pages to download.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageQuality {
+    /// The pages as uploaded, often large PNGs.
+    #[default]
+    Original,
+    /// Compressed JPEGs, a fraction of the size.
+    DataSaver,
+}
+
+impl ImageQuality {
+    /// The URL path segment MangaDex serves this quality under.
+    pub fn path(&self) -> &'static str {
+        match self {
+            ImageQuality::Original => "data",
+            ImageQuality::DataSaver => "data-saver",
+        }
+    }
+}
+
 /// Image filenames and content hash for a chapter.
 #[derive(Deserialize, Debug, Clone)]
 pub struct ImageAttributes {
@@ -109,10 +309,145 @@ pub struct ImageAttributes {
     pub data_saver: Vec<String>,
 }
 
+impl ImageAttributes {
+    /// `quality`, or [`ImageQuality::Original`] if MangaDex has no
+    /// data-saver versions of this chapter.
+    pub fn available(&self, quality: ImageQuality) -> ImageQuality {
+        if self.data_saver.is_empty() {
+            ImageQuality::Original
+        } else {
+            quality
+        }
+    }
+
+    /// Filenames for `quality`.
+    pub fn files(&self, quality: ImageQuality) -> &[String] {
+        match quality {
+            ImageQuality::Original => &self.data,
+            ImageQuality::DataSaver => &self.data_saver,
+        }
+    }
+
+    /// Filename of the page at `index` in `quality`, with the quality it is
+    /// actually in, or `None` if out of range.
+    ///
+    /// MangaDex sometimes lists fewer data-saver files than originals, so a
+    /// page missing from the data-saver list falls back to its original.
+    pub fn page_file(&self, index: usize, quality: ImageQuality) -> Option<(ImageQuality, &str)> {
+        match self.files(quality).get(index) {
+            Some(file) => Some((quality, file)),
+            None => Some((ImageQuality::Original, self.data.get(index)?)),
+        }
+    }
+}
+
+/// @Home server assignments by chapter ID, each valid for a fixed TTL.
+pub(crate) struct AtHomeCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, ImageDataResponse)>>,
+}
+
+impl AtHomeCache {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        AtHomeCache {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The assignment for `chapter_id`, unless it is missing or expired.
+    pub(crate) fn get(&self, chapter_id: &str) -> Option<ImageDataResponse> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let (fetched, data) = entries.get(chapter_id)?;
+        (fetched.elapsed() < self.ttl).then(|| data.clone())
+    }
+
+    /// Stores a fresh assignment, dropping any that have expired.
+    pub(crate) fn insert(&self, chapter_id: &str, data: ImageDataResponse) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|_, (fetched, _)| fetched.elapsed() < self.ttl);
+        entries.insert(chapter_id.to_string(), (Instant::now(), data));
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use super::*;
     use crate::client::MangaDexClient;
 
+    #[test]
+    fn page_hashes_are_checked_against_the_filename() {
+        // SHA-256 of the empty string.
+        let empty = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        let url = format!("https://node.mangadex.network/data/hash/A1-{empty}.jpg");
+        assert_eq!(filename_sha256(&url), Some(empty));
+        assert!(verify_page_hash(&url, b"").is_ok());
+        assert!(matches!(
+            verify_page_hash(&url, b"truncated"),
+            Err(YomuError::Integrity { .. })
+        ));
+        // Cover art and other files without a hash are not checked.
+        assert_eq!(filename_sha256("cover-0f1e.jpg"), None);
+        assert!(verify_page_hash("https://uploads.mangadex.org/covers/x/cover.jpg", b"x").is_ok());
+    }
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        DynamicImage::new_luma8(width, height)
+            .write_to(&mut Cursor::new(&mut bytes), image::ImageFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn decode_limits_reject_oversized_images() {
+        let limits = DecodeLimits::default();
+        assert_eq!(limits.decode(&png(8, 8)).unwrap().width(), 8);
+        // A few KB of PNG that would decode to a 40000 pixel tall bitmap.
+        assert!(matches!(
+            limits.decode(&png(1, 40_000)),
+            Err(YomuError::SizeLimit(SizeLimit::Dimensions {
+                width: 1,
+                height: 40_000
+            }))
+        ));
+        let tight = DecodeLimits {
+            max_alloc: 1024,
+            ..limits
+        };
+        assert!(matches!(
+            tight.decode(&png(100, 100)),
+            Err(YomuError::SizeLimit(SizeLimit::Allocation { .. }))
+        ));
+    }
+
+    #[test]
+    fn at_home_cache_honours_its_ttl() {
+        let data = ImageDataResponse {
+            result: "ok".to_string(),
+            base_url: "https://node.mangadex.network".to_string(),
+            chapter: ImageAttributes {
+                hash: "hash".to_string(),
+                data: Vec::new(),
+                data_saver: Vec::new(),
+            },
+        };
+        let cache = AtHomeCache::new(AT_HOME_TTL);
+        cache.insert("chapter", data.clone());
+        assert_eq!(
+            cache.get("chapter").map(|d| d.base_url),
+            Some(data.base_url.clone())
+        );
+        assert!(cache.get("other").is_none());
+
+        let disabled = AtHomeCache::new(Duration::ZERO);
+        disabled.insert("chapter", data);
+        assert!(disabled.get("chapter").is_none());
+    }
+
     #[tokio::test]
     #[ignore = "requires live network access"]
     async fn test_img_download() {
@@ -2,14 +2,33 @@ pub mod ascii;
 pub mod chapter;
 pub mod client;
 pub mod error;
+pub mod hosts;
 pub mod image;
+pub mod manga;
+pub mod query;
+pub mod ratelimit;
+pub mod reader;
+pub mod relationship;
+pub mod report;
+pub mod retry;
 pub mod search;
+pub mod tag;
+#[cfg(test)]
+mod test_support;
 
-pub use chapter::{ChapterAttributes, ChapterClient, ChapterData};
-pub use client::MangaDexClient;
-pub use error::{Result, YomuError};
-pub use image::{ImageAttributes, ImageClient, ImageDataResponse};
-pub use search::{MangaAttributes, MangaData, SearchClient};
+pub use chapter::{ChapterAttributes, ChapterClient, ChapterData, ChapterFeed};
+pub use client::{MangaDexClient, MangaDexClientBuilder};
+pub use error::{ApiError, Result, SizeLimit, YomuError};
+pub use hosts::HostAllowlist;
+pub use image::{DecodeLimits, ImageAttributes, ImageClient, ImageDataResponse, ImageQuality};
+pub use manga::{MangaClient, MangaStatistics};
+pub use query::SearchQuery;
+pub use ratelimit::{Quota, RateLimits};
+pub use reader::{ChapterLoader, PageEvent, QualityMode, ServerKind};
+pub use relationship::{Include, Relationship, RelationshipAttributes, RelationshipKind};
+pub use retry::RetryPolicy;
+pub use search::{MangaAttributes, MangaData, SearchClient, SearchPage};
+pub use tag::{Tag, TagAttributes, TagClient};
 
 #[cfg(test)]
 mod tests {
@@ -30,10 +49,8 @@ mod tests {
 
         for (i, manga) in search_results.iter().take(3).enumerate() {
             println!("\n[{}] ID: {}", i + 1, manga.id);
-            if let Some(ref titles) = manga.attributes.title {
-                if let Some(en_title) = titles.get("en") {
-                    println!("    Title: {}", en_title);
-                }
+            if let Some(title) = manga.localized_title(&["en", "ja-ro"]) {
+                println!("    Title: {}", title);
             }
         }
 
@@ -46,7 +63,7 @@ mod tests {
         let client = MangaDexClient::new().unwrap();
         let chapter_client = client.chapter_client();
         let chpt_result = chapter_client
-            .fetch_chapter("a77742b1-befd-49a4-bff5-1ad4e6b0ef7b".into(), Some("en"))
+            .fetch_chapter("a77742b1-befd-49a4-bff5-1ad4e6b0ef7b", &["en"])
             .await
             .unwrap();
         println!("Found {} chapters", chpt_result.len());
@@ -63,6 +80,60 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    #[ignore = "requires live network access"]
+    async fn test_chapter_feed_is_paginated() {
+        let client = MangaDexClient::new().unwrap();
+        let chapter_client = client.chapter_client();
+        // One Piece has well over one feed page (500) of English chapters.
+        let mut feed = chapter_client.feed_pages("a1c7c817-4e59-43b7-9365-09675a149a6f", &["en"]);
+        let mut pages = 0;
+        while feed.next_page().await.unwrap().is_some() {
+            pages += 1;
+        }
+        println!("Walked {} pages of {:?} chapters", pages, feed.total());
+        assert!(pages > 1);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires live network access"]
+    async fn test_tags() {
+        let client = MangaDexClient::new().unwrap();
+        let tags = client.tag_client().fetch_tags().await.unwrap();
+        println!("Found {} tags", tags.len());
+        for tag in tags.iter().take(5) {
+            println!(
+                "{} ({}) | ID: {}",
+                tag.name("en").unwrap_or("?"),
+                tag.attributes.group,
+                tag.id
+            );
+        }
+        assert!(tags.iter().any(|t| t.name("en") == Some("Action")));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires live network access"]
+    async fn test_manga_details() {
+        let client = MangaDexClient::new().unwrap();
+        let manga_client = client.manga_client();
+        let manga_id = "a77742b1-befd-49a4-bff5-1ad4e6b0ef7b";
+        let manga = manga_client.fetch_manga(manga_id).await.unwrap();
+        let stats = manga_client.fetch_statistics(manga_id).await.unwrap();
+        println!(
+            "Authors: {:?} | Artists: {:?}",
+            manga.author_names(),
+            manga.artist_names()
+        );
+        println!("Cover: {:?}", manga.cover_url());
+        println!(
+            "Rating: {:?} | Follows: {:?}",
+            stats.rating.bayesian, stats.follows
+        );
+        assert!(manga.cover_url().is_some());
+        assert!(!manga.author_names().is_empty());
+    }
+
     #[tokio::test]
     #[ignore = "requires live network access"]
     async fn fetch_image() {
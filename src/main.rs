@@ -1,12 +1,18 @@
 mod app;
+mod filters;
+mod notifications;
+mod settings;
 use app::App;
 use app::AppScreen;
+use app::ChapterRow;
+use app::{PendingRequest, ProtoTasks, RequestKind};
 use crossterm::event::KeyEvent;
 use crossterm::{
     ExecutableCommand,
     event::KeyCode,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
+use filters::FilterRow;
 use ratatui::layout::Rect;
 use ratatui::widgets::ListState;
 use ratatui::widgets::Paragraph;
@@ -15,17 +21,55 @@ use ratatui::{
     widgets::{Block, Borders, Gauge},
 };
 use ratatui_image::protocol::Protocol;
+use settings::GroupRef;
 use std::io::stdout;
-use std::sync::Arc;
-use tokio::sync::{mpsc, Semaphore};
-use yomu::MangaDexClient;
+use std::time::Instant;
+use tokio::sync::mpsc;
+use yomu::SearchQuery;
 use yomu::image::ImageDataResponse;
+use yomu::search::{SEARCH_PAGE_LIMIT, SearchPage};
+use yomu::{
+    ChapterData, ChapterLoader, ImageQuality, Include, MangaData, MangaDexClient, MangaStatistics,
+    PageEvent, QualityMode, ServerKind, Tag, YomuError,
+};
+
+/// Message from a background protocol-build task: (generation, page_idx, is_left_panel, protocol).
+type ProtoMsg = (u64, usize, bool, Protocol);
 
-/// Message from a background image-download task.
-/// The `Option` is `None` when the download permanently failed.
-type PageMsg = (usize, Option<image::DynamicImage>);
-/// Message from a background protocol-build task: (page_idx, is_left_panel, protocol).
-type ProtoMsg = (usize, bool, Protocol);
+/// Reply from a background API request, tagged with the request's ID.
+struct NetMsg {
+    id: u64,
+    reply: NetReply,
+}
+
+/// The result of each kind of background API request.
+enum NetReply {
+    /// A page of search results; `append` adds it to the current results
+    /// instead of replacing them.
+    Search {
+        query: SearchQuery,
+        append: bool,
+        result: yomu::Result<SearchPage>,
+    },
+    Tags(yomu::Result<Vec<Tag>>),
+    /// Full details of a manga. If the details request failed, `manga` is the
+    /// search result and `error` says why. Statistics and cover are optional
+    /// extras and are `None` if their requests failed.
+    MangaDetail {
+        manga: MangaData,
+        error: Option<YomuError>,
+        stats: Option<MangaStatistics>,
+        cover: Option<image::DynamicImage>,
+    },
+    Chapters(yomu::Result<Vec<ChapterData>>),
+    /// Page metadata for the chapter with ID `chapter_id`. The chapter list
+    /// may be re-filtered while the request is in flight, so the reply names
+    /// the chapter rather than its position.
+    ImageData {
+        chapter_id: String,
+        result: yomu::Result<ImageDataResponse>,
+    },
+}
 
 #[tokio::main]
 pub async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -33,26 +77,64 @@ pub async fn main() -> Result<(), Box<dyn std::error::Error>> {
     stdout().execute(EnterAlternateScreen)?;
 
     let mut app = app::App::new();
-    let client = MangaDexClient::new()?;
+    let client = MangaDexClient::builder()
+        .at_home_reports(app.settings.at_home_reports)
+        .build()?;
 
     let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
     terminal.clear()?;
 
     app.picker = ratatui_image::picker::Picker::from_query_stdio().ok();
 
-    let (page_tx, mut page_rx) = mpsc::channel::<PageMsg>(64);
     let (proto_tx, mut proto_rx) = mpsc::channel::<ProtoMsg>(32);
+    let (net_tx, mut net_rx) = mpsc::channel::<NetMsg>(8);
 
     loop {
-        // 1. Drain newly downloaded images
-        while let Ok((idx, maybe_img)) = page_rx.try_recv() {
-            // None means the download permanently failed; surface it in the UI.
-            let img = match maybe_img {
-                Some(img) => img,
-                None => {
-                    app.failed.insert(idx);
+        // 1. Drain newly downloaded pages
+        let events: Vec<PageEvent> = match app.loader.as_mut() {
+            Some(loader) => std::iter::from_fn(|| loader.try_next_event()).collect(),
+            None => Vec::new(),
+        };
+        for event in events {
+            let (idx, evicted) = match event {
+                PageEvent::Loaded { index, evicted } => (index, evicted),
+                PageEvent::ServerChanged { kind, .. } => {
+                    app.notifications.warn(match kind {
+                        ServerKind::Upstream => {
+                            "Image servers unreachable; using MangaDex's own server"
+                        }
+                        _ => "Image server stopped responding; switched to a new one",
+                    });
                     continue;
                 }
+                // Retrying will not help with these, so say why the page is missing.
+                PageEvent::Failed {
+                    index,
+                    error: YomuError::SizeLimit(_),
+                } => {
+                    app.notifications
+                        .warn(format!("Page {} is too large to display", index + 1));
+                    continue;
+                }
+                PageEvent::Failed {
+                    index,
+                    error: YomuError::UnsafeUrl { .. },
+                } => {
+                    app.notifications.warn(format!(
+                        "Page {} is on a server that is not allowed",
+                        index + 1
+                    ));
+                    continue;
+                }
+                // Other failures are recorded by the loader and shown in the page's panel.
+                PageEvent::Failed { .. } => continue,
+            };
+            if let Some(evict_idx) = evicted {
+                app.proto_cache.remove(&(evict_idx, true));
+                app.proto_cache.remove(&(evict_idx, false));
+            }
+            let Some(img) = app.loader.as_ref().and_then(|l| l.page(idx)).cloned() else {
+                continue;
             };
 
             if idx == app.current_page {
@@ -70,6 +152,7 @@ pub async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     idx,
                     false,
                     proto_tx.clone(),
+                    &mut app.proto_tasks,
                 );
             }
             if idx == app.current_page + 1 && app.last_left_area != Rect::default() {
@@ -80,6 +163,7 @@ pub async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     idx,
                     true,
                     proto_tx.clone(),
+                    &mut app.proto_tasks,
                 );
             }
             // Also pre-build for when this prefetched page becomes the current spread
@@ -92,6 +176,7 @@ pub async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     idx,
                     false,
                     proto_tx.clone(),
+                    &mut app.proto_tasks,
                 );
             }
             if idx == app.current_page + 3 && app.last_left_area != Rect::default() {
@@ -102,25 +187,16 @@ pub async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     idx,
                     true,
                     proto_tx.clone(),
+                    &mut app.proto_tasks,
                 );
             }
-            app.page_cache.insert(idx, img);
-            app.fetched.insert(idx); // mark complete so re-entering load_spread never re-fetches
-
-            // Evict the page farthest from the current position when the cache is full.
-            if app.page_cache.len() > app::MAX_CACHE_PAGES {
-                if let Some(&evict_idx) = app.page_cache.keys().max_by_key(|&&k| {
-                    (k as isize - app.current_page as isize).unsigned_abs()
-                }) {
-                    app.page_cache.remove(&evict_idx);
-                    app.proto_cache.remove(&(evict_idx, true));
-                    app.proto_cache.remove(&(evict_idx, false));
-                }
-            }
         }
 
         // 2. Drain pre-built protocols into the cache
-        while let Ok((idx, is_left, proto)) = proto_rx.try_recv() {
+        while let Ok((generation, idx, is_left, proto)) = proto_rx.try_recv() {
+            if generation != app.proto_tasks.generation {
+                continue;
+            }
             let area = if is_left {
                 app.last_left_area
             } else {
@@ -129,17 +205,41 @@ pub async fn main() -> Result<(), Box<dyn std::error::Error>> {
             app.proto_cache.insert((idx, is_left), (area, proto));
         }
 
+        // 3. Apply replies from background API requests
+        while let Ok(msg) = net_rx.try_recv() {
+            // Ignore replies to requests that were cancelled or superseded.
+            if app.pending.as_ref().is_none_or(|p| p.id != msg.id) {
+                continue;
+            }
+            app.pending = None;
+            handle_reply(&client, &mut app, msg.reply, &proto_tx);
+        }
+
+        // Reap finished protocol tasks so the set does not grow for the whole chapter.
+        while app.proto_tasks.set.try_join_next().is_some() {}
+
         terminal.draw(|frame| {
             render(&mut app, frame);
         })?;
 
-        if crossterm::event::poll(std::time::Duration::from_millis(16))? {
-            if let crossterm::event::Event::Key(key) = crossterm::event::read()? {
-                if key.code == crossterm::event::KeyCode::Esc {
-                    break;
+        if crossterm::event::poll(std::time::Duration::from_millis(16))?
+            && let crossterm::event::Event::Key(key) = crossterm::event::read()?
+        {
+            if app.history_open {
+                handle_history_event(&mut app, &key);
+            } else if key.code == crossterm::event::KeyCode::Esc {
+                // Esc cancels an in-flight search; otherwise it quits.
+                if app.is_pending(RequestKind::Search) {
+                    app.cancel_request();
+                    app.notifications.info("Search cancelled");
                 } else {
-                    handle_event(&client, &mut app, &key, &page_tx, &proto_tx).await;
+                    break;
                 }
+            } else if key.code == HISTORY_KEY {
+                app.history_open = true;
+                app.history_cursor = 0;
+            } else {
+                handle_event(&client, &mut app, &key, &proto_tx, &net_tx);
             }
         }
     }
@@ -153,9 +253,133 @@ fn render(app: &mut App, frame: &mut Frame<'_>) {
     match app.screen {
         AppScreen::Splash => draw_splash(app, frame),
         AppScreen::Search => draw_search(app, frame),
+        AppScreen::TagBrowser => draw_tag_browser(app, frame),
+        AppScreen::MangaDetail => draw_manga_detail(app, frame),
         AppScreen::ChapterList => draw_chapter_list(app, frame),
         AppScreen::Reading => draw_reading_page(app, frame),
     }
+    draw_spinner(app, frame);
+    draw_toasts(app, frame);
+    if app.history_open {
+        draw_history(app, frame);
+    }
+}
+
+/// Opens the notification history from any screen.
+const HISTORY_KEY: KeyCode = KeyCode::F(2);
+
+/// Stacks recent notifications in the bottom-right corner, newest at the bottom.
+fn draw_toasts(app: &App, frame: &mut Frame<'_>) {
+    let area = frame.area();
+    let width = area.width.min(60);
+    let mut bottom = area.bottom().saturating_sub(1);
+    let toasts: Vec<_> = app.notifications.toasts().collect();
+    for toast in toasts.into_iter().rev() {
+        let color = toast.severity.color();
+        let toast_area = Rect::new(
+            area.right().saturating_sub(width + 1),
+            bottom.saturating_sub(3),
+            width,
+            3,
+        );
+        if toast_area.y < area.y {
+            break;
+        }
+        frame.render_widget(ratatui::widgets::Clear, toast_area);
+        frame.render_widget(
+            Paragraph::new(toast.message.as_str())
+                .style(Style::default().fg(color))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(format!(" {} ", toast.severity.label()))
+                        .title_bottom(" [F2] history ")
+                        .border_style(Style::default().fg(color)),
+                ),
+            toast_area,
+        );
+        bottom = toast_area.y;
+    }
+}
+
+/// Renders every kept notification, newest first, in a popup over the current screen.
+fn draw_history(app: &App, frame: &mut Frame<'_>) {
+    let area = frame.area();
+    let popup = Rect::new(
+        area.x + area.width / 8,
+        area.y + area.height / 8,
+        area.width - area.width / 4,
+        area.height - area.height / 4,
+    );
+
+    let items: Vec<ratatui::widgets::ListItem> = app
+        .notifications
+        .history()
+        .map(|n| {
+            let age = n.created.elapsed().as_secs();
+            ratatui::widgets::ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!(" {:>4}s ago ", age),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(
+                    format!("{:<8}", n.severity.label()),
+                    Style::default()
+                        .fg(n.severity.color())
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(n.message.as_str()),
+            ]))
+        })
+        .collect();
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(app.history_cursor));
+
+    let list = ratatui::widgets::List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" 🔔 Notifications ({}) ", app.notifications.len()))
+                .title_bottom(" [↑/↓] scroll  [F2/Esc] close ")
+                .border_style(Style::default().fg(Color::Rgb(255, 105, 180))),
+        )
+        .highlight_style(Style::default().bg(Color::Rgb(40, 40, 60)));
+
+    frame.render_widget(ratatui::widgets::Clear, popup);
+    frame.render_stateful_widget(list, popup, &mut list_state);
+}
+
+/// Frames of the loading spinner, advanced every 80ms.
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Overlays a spinner in the top-right corner while an API request is pending.
+fn draw_spinner(app: &App, frame: &mut Frame<'_>) {
+    let Some(pending) = app.pending.as_ref() else {
+        return;
+    };
+    let tick = pending.started.elapsed().as_millis() / 80;
+    let spinner = SPINNER_FRAMES[tick as usize % SPINNER_FRAMES.len()];
+    let hint = if pending.kind == RequestKind::Search {
+        " [Esc] cancel"
+    } else {
+        ""
+    };
+    let text = format!(" {} {}{} ", spinner, pending.kind.label(), hint);
+
+    let area = frame.area();
+    let width = (text.chars().count() as u16).min(area.width);
+    let spinner_area = Rect::new(area.right().saturating_sub(width + 1), area.y, width, 1);
+    frame.render_widget(ratatui::widgets::Clear, spinner_area);
+    frame.render_widget(
+        Paragraph::new(text).style(
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Rgb(255, 200, 120))
+                .add_modifier(Modifier::BOLD),
+        ),
+        spinner_area,
+    );
 }
 
 /// Renders the anime-themed splash screen.
@@ -213,6 +437,7 @@ fn draw_splash(_app: &App, frame: &mut Frame<'_>) {
 • [Arrows] Navigate
 • [l / r]  Next / Prev Spread
 • [b]      To Chapter List
+• [Tab]    Search Filters
     "#;
     frame.render_widget(
         Paragraph::new(directions)
@@ -269,7 +494,8 @@ fn draw_search(app: &App, frame: &mut Frame<'_>) {
         .constraints([Constraint::Length(3), Constraint::Min(0)])
         .split(area);
     let search_area = layout[0];
-    let result_area = layout[1];
+    let [result_area, filter_area] =
+        Layout::horizontal([Constraint::Fill(1), Constraint::Length(34)]).areas(layout[1]);
 
     let mut list_state = ListState::default();
     list_state.select(Some(app.selected_index));
@@ -289,23 +515,18 @@ fn draw_search(app: &App, frame: &mut Frame<'_>) {
     let items: Vec<ratatui::widgets::ListItem> = app
         .search_result
         .iter()
-        .map(|m| {
-            let title = m
-                .attributes
-                .title
-                .as_ref()
-                .and_then(|t| t.get("en"))
-                .map(|t| t.as_str())
-                .unwrap_or("Unknown Title");
-            ratatui::widgets::ListItem::new(title)
-        })
+        .map(|m| ratatui::widgets::ListItem::new(app.manga_title(m)))
         .collect();
 
     let list = ratatui::widgets::List::new(items)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(" 📚 Results ")
+                .title(format!(
+                    " 📚 Results ({} of {}) ",
+                    app.search_result.len(),
+                    app.search_total
+                ))
                 .border_style(Style::default().fg(Color::Rgb(255, 105, 180))),
         )
         .highlight_style(
@@ -317,6 +538,279 @@ fn draw_search(app: &App, frame: &mut Frame<'_>) {
         .highlight_symbol(">> ");
 
     frame.render_stateful_widget(list, result_area, &mut list_state);
+
+    draw_filter_panel(app, frame, filter_area);
+}
+
+/// Renders the search filter panel beside the results list.
+fn draw_filter_panel(app: &App, frame: &mut Frame<'_>, area: Rect) {
+    let items: Vec<ratatui::widgets::ListItem> = FilterRow::ALL
+        .iter()
+        .map(|row| {
+            ratatui::widgets::ListItem::new(format!(
+                " {:<12}◂ {} ▸",
+                row.label(),
+                row.value(&app.search_filters)
+            ))
+        })
+        .collect();
+
+    let mut list_state = ListState::default();
+    if app.filter_panel_open {
+        list_state.select(Some(app.filter_cursor));
+    }
+
+    let border_color = if app.filter_panel_open {
+        Color::Cyan
+    } else {
+        Color::Rgb(90, 90, 110)
+    };
+    let list = ratatui::widgets::List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" ⚙ Filters [Tab] ")
+                .border_style(Style::default().fg(border_color)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::Cyan)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    frame.render_stateful_widget(list, area, &mut list_state);
+}
+
+/// Renders the tag catalogue grouped by tag group, marking included and excluded tags.
+fn draw_tag_browser(app: &App, frame: &mut Frame<'_>) {
+    let area = frame.area();
+    // Background
+    frame.render_widget(Block::default().bg(Color::Rgb(10, 10, 20)), area);
+
+    let [list_area, help_area] =
+        Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+
+    // Interleave a header row before each group; track where the cursor's tag lands.
+    let mut items: Vec<ratatui::widgets::ListItem> = Vec::new();
+    let mut selected_row = None;
+    let mut current_group: Option<&str> = None;
+    for (i, tag) in app.tags.iter().enumerate() {
+        let group = tag.attributes.group.as_str();
+        if current_group != Some(group) {
+            current_group = Some(group);
+            items.push(ratatui::widgets::ListItem::new(Line::styled(
+                format!("── {} ──", group.to_uppercase()),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            )));
+        }
+        if i == app.tag_cursor {
+            selected_row = Some(items.len());
+        }
+        let (marker, color) = if app.search_filters.included_tags.contains(&tag.id) {
+            ("[+]", Color::Green)
+        } else if app.search_filters.excluded_tags.contains(&tag.id) {
+            ("[-]", Color::Red)
+        } else {
+            ("[ ]", Color::White)
+        };
+        items.push(ratatui::widgets::ListItem::new(Line::styled(
+            format!("{} {}", marker, tag.name("en").unwrap_or("Unnamed Tag")),
+            Style::default().fg(color),
+        )));
+    }
+
+    let mut list_state = ListState::default();
+    list_state.select(selected_row);
+
+    let list = ratatui::widgets::List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" 🏷 Tags ")
+                .border_style(Style::default().fg(Color::Rgb(255, 105, 180))),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::Rgb(255, 105, 180))
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(">> ");
+
+    frame.render_stateful_widget(list, list_area, &mut list_state);
+
+    Paragraph::new("[Space] cycle  [i] include  [x] exclude  [c] clear  [Enter] search  [b] back")
+        .style(Style::default().fg(Color::Rgb(200, 200, 200)))
+        .centered()
+        .render(help_area, frame.buffer_mut());
+}
+
+/// Renders the manga detail screen: cover on the left, metadata and the
+/// wrapped description on the right.
+fn draw_manga_detail(app: &mut App, frame: &mut Frame<'_>) {
+    let area = frame.area();
+    // Background
+    frame.render_widget(Block::default().bg(Color::Rgb(10, 10, 20)), area);
+
+    let [header_area, body_area, help_area] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Fill(1),
+        Constraint::Length(1),
+    ])
+    .areas(area);
+    let [cover_area, info_area] =
+        Layout::horizontal([Constraint::Length(32), Constraint::Fill(1)]).areas(body_area);
+
+    let Some(manga) = app.selected_manga.as_ref() else {
+        return;
+    };
+    let attributes = &manga.attributes;
+
+    let manga_title = app.manga_title(manga);
+    frame.render_widget(
+        Paragraph::new(format!(" {}", manga_title))
+            .style(Style::default().add_modifier(Modifier::BOLD))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan)),
+            ),
+        header_area,
+    );
+
+    let label = Style::default().fg(Color::Rgb(255, 105, 180));
+    let field = |name: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{:<12}", name), label),
+            Span::raw(value),
+        ])
+    };
+    let join_or_unknown = |names: Vec<&str>| {
+        if names.is_empty() {
+            "Unknown".to_string()
+        } else {
+            names.join(", ")
+        }
+    };
+
+    let mut lines = vec![
+        field("Author", join_or_unknown(manga.author_names())),
+        field("Artist", join_or_unknown(manga.artist_names())),
+        field(
+            "Status",
+            attributes.status.clone().unwrap_or_else(|| "?".to_string()),
+        ),
+        field(
+            "Year",
+            attributes
+                .year
+                .map_or_else(|| "?".to_string(), |y| y.to_string()),
+        ),
+        field(
+            "Demographic",
+            attributes
+                .publication_demographic
+                .clone()
+                .unwrap_or_else(|| "?".to_string()),
+        ),
+    ];
+    if let Some(stats) = &app.manga_stats {
+        let rating = stats
+            .rating
+            .bayesian
+            .or(stats.rating.average)
+            .map_or_else(|| "?".to_string(), |r| format!("{:.2} / 10", r));
+        lines.push(field("Rating", rating));
+        lines.push(field(
+            "Follows",
+            stats
+                .follows
+                .map_or_else(|| "?".to_string(), |f| f.to_string()),
+        ));
+    }
+    let tags: Vec<&str> = attributes
+        .tags
+        .iter()
+        .filter_map(|t| t.name("en"))
+        .collect();
+    lines.push(field("Tags", join_or_unknown(tags)));
+    lines.push(Line::default());
+
+    let description = attributes.description.as_ref().and_then(|d| {
+        d.get(&app.ui_language)
+            .or_else(|| d.get("en"))
+            .or_else(|| d.values().next())
+    });
+    lines.push(Line::raw(
+        description
+            .map(|d| d.as_str())
+            .unwrap_or("No description available."),
+    ));
+
+    frame.render_widget(
+        Paragraph::new(lines)
+            .wrap(ratatui::widgets::Wrap { trim: false })
+            .scroll((app.detail_scroll, 0))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" 📖 Details ")
+                    .border_style(Style::default().fg(Color::Rgb(255, 105, 180))),
+            ),
+        info_area,
+    );
+
+    render_cover(app, frame, cover_area);
+
+    Paragraph::new("[Enter] Chapters  [↑/↓] Scroll  [b] Back")
+        .style(Style::default().fg(Color::Rgb(200, 200, 200)))
+        .centered()
+        .render(help_area, frame.buffer_mut());
+}
+
+/// Renders the selected manga's cover, re-encoding it when the area changes size.
+fn render_cover(app: &mut App, frame: &mut Frame<'_>, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let Some(cover) = app.cover.clone() else {
+        frame.render_widget(Paragraph::new("No cover").centered(), inner);
+        return;
+    };
+    let cache_valid = app
+        .cover_proto
+        .as_ref()
+        .is_some_and(|(a, _)| a.width == inner.width && a.height == inner.height);
+    if !cache_valid {
+        let picker = app
+            .picker
+            .clone()
+            .unwrap_or_else(ratatui_image::picker::Picker::halfblocks);
+        app.cover_proto = picker
+            .new_protocol(
+                cover,
+                inner,
+                ratatui_image::Resize::Fit(Some(ratatui_image::FilterType::Triangle)),
+            )
+            .ok()
+            .map(|p| (inner, p));
+    }
+    if let Some((_, p)) = &app.cover_proto {
+        let actual_area = p.area();
+        let render_area = Rect::new(
+            inner.x + inner.width.saturating_sub(actual_area.width) / 2,
+            inner.y,
+            actual_area.width,
+            actual_area.height,
+        );
+        frame.render_widget(ratatui_image::Image::new(p), render_area);
+    }
 }
 
 /// Renders the list of chapters for a selected manga.
@@ -331,19 +825,38 @@ fn draw_chapter_list(app: &App, frame: &mut Frame<'_>) {
         .split(area);
 
     let header_area = layout[0];
-    let list_area = layout[1];
+    let mut list_area = layout[1];
+    if app.language_panel_open {
+        let [chapters_area, panel_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(26)]).areas(list_area);
+        list_area = chapters_area;
+        draw_language_panel(app, frame, panel_area);
+    }
 
     let manga_title = app
         .selected_manga
         .as_ref()
-        .and_then(|m| m.attributes.title.as_ref())
-        .and_then(|t| t.get("en"))
-        .map(|t| t.as_str())
+        .map(|m| app.manga_title(m))
         .unwrap_or("Unknown Manga");
 
+    let mut header = format!(
+        " Chapters for: {}  |  🌐 {}",
+        manga_title,
+        app.settings.languages.join(" › ")
+    );
+    if let Some(group) = app.preferred_group() {
+        header.push_str(&format!("  |  ★ Preferred: {}", group.name));
+    }
+    if !app.settings.blocked_groups.is_empty() {
+        header.push_str(&format!(
+            "  |  {} group(s) blocked",
+            app.settings.blocked_groups.len()
+        ));
+    }
+
     // Header
     frame.render_widget(
-        Paragraph::new(format!(" Chapters for: {}", manga_title)).block(
+        Paragraph::new(header).block(
             Block::default()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan)),
@@ -356,17 +869,71 @@ fn draw_chapter_list(app: &App, frame: &mut Frame<'_>) {
 
     // Chapter List
     let items: Vec<ratatui::widgets::ListItem> = app
-        .chapters
-        .iter()
-        .map(|c| {
-            let title = c
-                .attributes
-                .title
-                .as_deref()
-                .unwrap_or("Untitled Chapter");
-            let vol = c.attributes.volume.as_deref().unwrap_or("?");
-            let chap = c.attributes.chapter.as_deref().unwrap_or("?");
-            ratatui::widgets::ListItem::new(format!(" Vol. {} Ch. {} - {}", vol, chap, title))
+        .chapter_rows()
+        .into_iter()
+        .filter_map(|row| match row {
+            ChapterRow::Volume(v) => {
+                let volume = &app.volumes[v];
+                let marker = if app.collapsed_volumes.contains(&volume.volume) {
+                    "▸"
+                } else {
+                    "▾"
+                };
+                let label = match &volume.volume {
+                    Some(number) => format!("Volume {}", number),
+                    None => "No volume".to_string(),
+                };
+                Some(ratatui::widgets::ListItem::new(Line::from(Span::styled(
+                    format!(" {} {} ({} chapters)", marker, label, volume.chapters.len()),
+                    Style::default()
+                        .fg(Color::Rgb(255, 200, 120))
+                        .add_modifier(Modifier::BOLD),
+                ))))
+            }
+            ChapterRow::Chapter(v, c) => {
+                let entry = &app.volumes[v].chapters[c];
+                let chosen = app.chosen_upload(v, c)?;
+                let chapter = &app.chapters[chosen];
+                let title = chapter
+                    .attributes
+                    .title
+                    .as_deref()
+                    .unwrap_or("Untitled Chapter");
+                let chap = entry.chapter.as_deref().unwrap_or("?");
+                let groups = chapter.scanlation_group_names();
+                let group = if groups.is_empty() {
+                    "No Group".to_string()
+                } else {
+                    groups.join(" & ")
+                };
+                let preferred = app
+                    .preferred_group()
+                    .is_some_and(|g| chapter.is_by_group(&g.id));
+                let language = chapter
+                    .attributes
+                    .translated_language
+                    .as_deref()
+                    .unwrap_or("??");
+                let mut spans = vec![
+                    Span::raw(format!("    Ch. {} - {} ", chap, title)),
+                    Span::styled(
+                        format!("({}) ", language),
+                        Style::default().fg(Color::Rgb(180, 255, 160)),
+                    ),
+                    Span::styled(
+                        format!("[{}{}]", if preferred { "★ " } else { "" }, group),
+                        Style::default().fg(Color::Rgb(120, 200, 255)),
+                    ),
+                ];
+                if entry.uploads.len() > 1 {
+                    let position = entry.uploads.iter().position(|&i| i == chosen).unwrap_or(0);
+                    spans.push(Span::styled(
+                        format!(" ({}/{} ◂▸)", position + 1, entry.uploads.len()),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                Some(ratatui::widgets::ListItem::new(Line::from(spans)))
+            }
         })
         .collect();
 
@@ -375,6 +942,9 @@ fn draw_chapter_list(app: &App, frame: &mut Frame<'_>) {
             Block::default()
                 .borders(Borders::ALL)
                 .title(" 📑 Select Chapter ")
+                .title_bottom(
+                    " [Space] fold volume  [←/→] switch upload  [l] languages  [p] prefer group  [x] block group  [U] unblock all ",
+                )
                 .border_style(Style::default().fg(Color::Rgb(255, 105, 180))),
         )
         .highlight_style(
@@ -388,6 +958,47 @@ fn draw_chapter_list(app: &App, frame: &mut Frame<'_>) {
     frame.render_stateful_widget(list, list_area, &mut list_state);
 }
 
+/// Renders the chapter language panel: chosen languages numbered by
+/// priority, followed by the ones that can be added.
+fn draw_language_panel(app: &App, frame: &mut Frame<'_>, area: Rect) {
+    let items: Vec<ratatui::widgets::ListItem> = app
+        .settings
+        .language_rows()
+        .iter()
+        .map(
+            |language| match app.settings.languages.iter().position(|l| l == language) {
+                Some(rank) => {
+                    ratatui::widgets::ListItem::new(format!(" {}. {}", rank + 1, language))
+                }
+                None => ratatui::widgets::ListItem::new(Line::styled(
+                    format!("    {}", language),
+                    Style::default().fg(Color::DarkGray),
+                )),
+            },
+        )
+        .collect();
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(app.language_cursor));
+
+    let list = ratatui::widgets::List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" 🌐 Languages [l] ")
+                .title_bottom(" [Space] add/remove  [K/J] move ")
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::Cyan)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    frame.render_stateful_widget(list, area, &mut list_state);
+}
+
 /// Renders the high-resolution two-page reading spread.
 ///
 /// This function records the current layout areas so background tasks can
@@ -411,16 +1022,28 @@ fn draw_reading_page(app: &mut App, frame: &mut Frame<'_>) {
     app.last_right_area = right;
     app.last_left_area = left;
 
-    let (page_info, progress) = if let Some(img_data) = &app.image_data {
-        let total = img_data.chapter.data.len();
-        let cached = app.page_cache.len();
+    let (page_info, progress) = if let Some(loader) = &app.loader {
+        let total = loader.page_count();
+        let cached = loader.cached_count();
         let p = if total > 0 { cached as f64 / total as f64 } else { 0.0 };
+        let failed = match loader.failed_count() {
+            0 => String::new(),
+            n => format!(" - {n} failed, 'r' to retry"),
+        };
+        let quality = match (loader.quality_mode(), loader.quality()) {
+            (QualityMode::Auto, ImageQuality::DataSaver) => " [auto: data saver]",
+            (QualityMode::Auto, ImageQuality::Original) => " [auto]",
+            (_, ImageQuality::DataSaver) => " [data saver]",
+            (_, ImageQuality::Original) => "",
+        };
         (
             format!(
-                "Pages {} & {} / {} - Press 'b' to go back",
+                "Pages {} & {} / {}{}{} - Press 'b' to go back",
                 app.current_page + 1,
                 app.current_page + 2,
-                total
+                total,
+                quality,
+                failed
             ),
             p
         )
@@ -428,12 +1051,17 @@ fn draw_reading_page(app: &mut App, frame: &mut Frame<'_>) {
         ("Reading Mode - Press 'b' to go back".to_string(), 0.0)
     };
 
-    let manga_display = match app.chapters.get(app.selected_index) {
-        Some(chapter) => chapter
-            .attributes
-            .title
-            .clone()
-            .unwrap_or_else(|| "Untitled".to_string()),
+    let manga_title = app
+        .selected_manga
+        .as_ref()
+        .map(|m| app.manga_title(m))
+        .unwrap_or("Unknown Manga");
+    let manga_display = match app.current_chapter.and_then(|i| app.chapters.get(i)) {
+        Some(chapter) => format!(
+            "{} — {}",
+            manga_title,
+            chapter.attributes.title.as_deref().unwrap_or("Untitled")
+        ),
         None => "No chapters available".to_string(),
     };
     let display = format!(" Currently reading: {}", manga_display);
@@ -473,7 +1101,7 @@ fn render_panel(app: &mut App, frame: &mut Frame<'_>, area: Rect, page_idx: usiz
     let cache_valid = app
         .proto_cache
         .get(&(page_idx, is_left))
-        .map_or(false, |(cached_area, _)| {
+        .is_some_and(|(cached_area, _)| {
             cached_area.width == area.width && cached_area.height == area.height
         });
 
@@ -516,9 +1144,9 @@ fn render_panel(app: &mut App, frame: &mut Frame<'_>, area: Rect, page_idx: usiz
             actual_area.height,
         );
         frame.render_widget(image_widget, render_area);
-    } else if app.failed.contains(&page_idx) {
+    } else if app.loader.as_ref().is_some_and(|l| l.is_failed(page_idx)) {
         frame.render_widget(
-            Paragraph::new("Error loading page")
+            Paragraph::new("Error loading page - press 'r' to retry")
                 .centered()
                 .style(Style::default().fg(Color::Red)),
             area,
@@ -551,14 +1179,15 @@ fn render_kawaii_strip(frame: &mut Frame<'_>, area: Rect) {
 
 /// Handles keyboard events and updates the application state.
 ///
-/// Most transitions involve asynchronous operations (search, fetch) which are
-/// managed within this function.
-async fn handle_event(
+/// API requests (search, fetch) are spawned as background tasks so the UI
+/// keeps drawing; their replies arrive on `net_tx` and are applied by
+/// [`handle_reply`].
+fn handle_event(
     client: &MangaDexClient,
     app: &mut App,
     key: &KeyEvent,
-    page_tx: &mpsc::Sender<PageMsg>,
     proto_tx: &mpsc::Sender<ProtoMsg>,
+    net_tx: &mpsc::Sender<NetMsg>,
 ) {
     match app.screen {
         AppScreen::Splash => {
@@ -566,7 +1195,13 @@ async fn handle_event(
             app.screen = AppScreen::Search;
             app.selected_index = 0;
         }
+        AppScreen::Search if app.filter_panel_open => {
+            handle_filter_event(client, app, key, net_tx);
+        }
         AppScreen::Search => match key.code {
+            KeyCode::Tab => {
+                app.filter_panel_open = true;
+            }
             KeyCode::Char(c) => {
                 app.search_input.push(c);
             }
@@ -574,103 +1209,200 @@ async fn handle_event(
                 app.search_input.pop();
             }
             KeyCode::Enter => {
-                if !app.search_input.is_empty() && app.search_input != app.last_search_query {
-                    let search_client = client.search_client();
-                    let result = search_client.search(app.search_input.clone()).await;
-                    if let Ok(result) = result {
-                        app.search_result = result;
-                        app.last_search_query = app.search_input.clone();
-                    } else if let Err(e) = result {
-                        eprintln!("Error: {}", e);
-                    }
-                } else if !app.search_result.is_empty() {
-                    app.selected_manga = Some(app.search_result[app.selected_index].clone());
-                    let chapter_client = client.chapter_client();
-
-                    let manga_id = app.selected_manga.as_ref().map(|manga| &manga.id);
-                    let Some(manga_id_str) = manga_id else {
-                        eprintln!("Value of id was none. exiting..");
-                        return;
-                    };
-                    let chapter_result = chapter_client
-                        .fetch_chapter(manga_id_str.as_str(), Some("en"))
-                        .await;
-                    let Ok(chapter_data) = chapter_result else {
-                        eprint!("There was an error fetching the chapter data");
-                        return;
-                    };
-                    app.chapters = chapter_data;
-                    app.screen = AppScreen::ChapterList;
-                    app.selected_index = 0;
+                let query = app.pending_query();
+                let searchable = query.title.is_some() || query.has_filters();
+                if searchable && app.last_search.as_ref() != Some(&query) {
+                    run_search(client, app, query, 0, net_tx);
+                } else if let Some(manga) = app.search_result.get(app.selected_index) {
+                    let manga = manga.clone();
+                    open_manga_detail(client, app, manga, net_tx);
                 }
             }
             KeyCode::Up => app.selected_index = app.selected_index.saturating_sub(1),
             KeyCode::Down => {
                 if app.selected_index + 1 < app.search_result.len() {
                     app.selected_index += 1;
+                } else if app.pending.is_none()
+                    && let (Some(offset), Some(query)) =
+                        (app.search_next_offset, app.last_search.clone())
+                {
+                    // Cursor reached the bottom: load the next page of results.
+                    run_search(client, app, query, offset, net_tx);
                 }
             }
             _ => {}
         },
-        AppScreen::ChapterList => match key.code {
+        AppScreen::TagBrowser => match key.code {
             KeyCode::Char('b') => {
                 app.screen = AppScreen::Search;
             }
-            KeyCode::Up => app.selected_index = app.selected_index.saturating_sub(1),
-            KeyCode::Down => {
-                if app.selected_index + 1 < app.chapters.len() {
-                    app.selected_index += 1;
-                }
+            KeyCode::Up => app.tag_cursor = app.tag_cursor.saturating_sub(1),
+            KeyCode::Down if app.tag_cursor + 1 < app.tags.len() => {
+                app.tag_cursor += 1;
+            }
+            KeyCode::Char(' ') => app.cycle_tag(app.tag_cursor),
+            KeyCode::Char('i') => app.toggle_tag(app.tag_cursor, true),
+            KeyCode::Char('x') => app.toggle_tag(app.tag_cursor, false),
+            KeyCode::Char('c') => {
+                app.search_filters.included_tags.clear();
+                app.search_filters.excluded_tags.clear();
+            }
+            KeyCode::Enter => {
+                app.screen = AppScreen::Search;
+                let query = app.pending_query();
+                run_search(client, app, query, 0, net_tx);
+            }
+            _ => {}
+        },
+        AppScreen::MangaDetail => match key.code {
+            KeyCode::Char('b') => {
+                // Put the cursor back on the manga we came from.
+                let selected_id = app.selected_manga.as_ref().map(|m| m.id.as_str());
+                app.selected_index = app
+                    .search_result
+                    .iter()
+                    .position(|m| Some(m.id.as_str()) == selected_id)
+                    .unwrap_or(0);
+                app.screen = AppScreen::Search;
             }
+            KeyCode::Up => app.detail_scroll = app.detail_scroll.saturating_sub(1),
+            KeyCode::Down => app.detail_scroll = app.detail_scroll.saturating_add(1),
             KeyCode::Enter => {
-                let image_client = client.image_client();
-                let chapter_id = &app.chapters[app.selected_index].id;
-                let result = image_client.fetch_image_data(chapter_id).await;
-                let Ok(image_data) = result else {
-                    eprintln!("There was an error getting the image data: {:?}", result);
+                app.selected_index = 0;
+                app.collapsed_volumes.clear();
+                app.upload_choice.clear();
+                load_chapters(client, app, net_tx);
+            }
+            _ => {}
+        },
+        AppScreen::ChapterList if app.language_panel_open => {
+            handle_language_event(client, app, key, net_tx);
+        }
+        AppScreen::ChapterList => match key.code {
+            KeyCode::Char('b') => {
+                app.screen = AppScreen::MangaDetail;
+            }
+            KeyCode::Char('l') => {
+                app.language_panel_open = true;
+                app.language_cursor = 0;
+                app.language_snapshot = Some(app.settings.languages.clone());
+            }
+            KeyCode::Char('p') => {
+                // Toggle the selected chapter's group as this manga's preferred group.
+                let (Some(manga), Some(chapter)) = (
+                    app.selected_manga.as_ref(),
+                    app.selected_chapter().and_then(|i| app.chapters.get(i)),
+                ) else {
+                    return;
+                };
+                let manga_id = manga.id.clone();
+                let group = chapter_group(chapter);
+                if app.preferred_group() == group.as_ref() {
+                    app.settings.preferred_groups.remove(&manga_id);
+                } else if let Some(group) = group {
+                    app.settings.preferred_groups.insert(manga_id, group);
+                }
+                app.save_settings();
+                app.refresh_chapters();
+            }
+            KeyCode::Char('x') => {
+                // Block the selected chapter's group everywhere.
+                let Some(group) = app
+                    .selected_chapter()
+                    .and_then(|i| app.chapters.get(i))
+                    .and_then(chapter_group)
+                else {
+                    return;
+                };
+                if !app.settings.blocked_groups.contains(&group) {
+                    app.settings.blocked_groups.push(group);
+                    app.save_settings();
+                }
+                app.refresh_chapters();
+            }
+            KeyCode::Char('U') => {
+                // Unblock every group; blocked chapters were never fetched, so reload.
+                app.settings.blocked_groups.clear();
+                app.save_settings();
+                load_chapters(client, app, net_tx);
+            }
+            KeyCode::Up => app.selected_index = app.selected_index.saturating_sub(1),
+            KeyCode::Down if app.selected_index + 1 < app.chapter_rows().len() => {
+                app.selected_index += 1;
+            }
+            KeyCode::Char(' ') => toggle_selected_volume(app),
+            KeyCode::Left | KeyCode::Right => {
+                // Switch between competing uploads of the selected chapter number.
+                let Some(ChapterRow::Chapter(v, c)) =
+                    app.chapter_rows().get(app.selected_index).copied()
+                else {
                     return;
                 };
-                app.image_data = Some(image_data);
-                let img_data = app
-                    .image_data
-                    .as_ref()
-                    .expect("Image data should be present");
-                if img_data.chapter.data.is_empty() {
-                    eprint!("there are no pages to display");
+                let entry = &app.volumes[v].chapters[c];
+                let (Some(number), count) = (entry.chapter.clone(), entry.uploads.len()) else {
                     return;
+                };
+                if count > 1 {
+                    let current = app.upload_choice.get(&number).copied().unwrap_or(0);
+                    let next = if key.code == KeyCode::Right {
+                        (current + 1) % count
+                    } else {
+                        (current + count - 1) % count
+                    };
+                    app.upload_choice.insert(number, next);
                 }
-                app.current_page = 0;
-                app.page_cache.clear();
-                app.proto_cache.clear();
-                app.fetched.clear();
-                app.failed.clear();
-                app.page_left = None;
-                app.page_right = None;
-
-                let img_data = img_data.clone();
-                load_spread(app, client.http_client(), &img_data, page_tx, proto_tx).await;
-                app.screen = AppScreen::Reading;
+            }
+            KeyCode::Enter => {
+                let Some(chapter_index) = app.selected_chapter() else {
+                    toggle_selected_volume(app);
+                    return;
+                };
+                let client = client.clone();
+                let chapter_id = app.chapters[chapter_index].id.clone();
+                start_request(app, RequestKind::ImageData, net_tx, async move {
+                    let result = client.image_client().fetch_image_data(&chapter_id).await;
+                    NetReply::ImageData { chapter_id, result }
+                });
             }
             _ => {}
         },
         AppScreen::Reading => match key.code {
             KeyCode::Char('b') => {
+                // Stop downloading the chapter we are leaving.
+                app.close_chapter();
                 app.screen = AppScreen::ChapterList;
             }
             KeyCode::Char('l') | KeyCode::Right => {
-                if let Some(img_data) = app.image_data.clone() {
-                    if app.current_page + 2 < img_data.chapter.data.len() {
-                        app.current_page += 2;
-                        load_spread(app, client.http_client(), &img_data, page_tx, proto_tx).await;
-                    }
+                if let Some(loader) = &app.loader
+                    && app.current_page + 2 < loader.page_count()
+                {
+                    app.current_page += 2;
+                    load_spread(app, proto_tx);
                 }
             }
-            KeyCode::Char('h') | KeyCode::Left => {
-                if let Some(img_data) = app.image_data.clone() {
-                    if app.current_page >= 2 {
-                        app.current_page -= 2;
-                        load_spread(app, client.http_client(), &img_data, page_tx, proto_tx).await;
-                    }
+            KeyCode::Char('h') | KeyCode::Left if app.loader.is_some() && app.current_page >= 2 => {
+                app.current_page -= 2;
+                load_spread(app, proto_tx);
+            }
+            KeyCode::Char('d') => {
+                let modes = QualityMode::ALL;
+                let current = modes.iter().position(|m| *m == app.settings.quality_mode);
+                let mode = modes[current.map_or(0, |i| (i + 1) % modes.len())];
+                app.settings.quality_mode = mode;
+                if let Some(loader) = &app.loader {
+                    loader.set_quality_mode(mode);
+                }
+                app.notifications
+                    .info(format!("Image quality: {}", quality_label(mode)));
+                app.save_settings();
+            }
+            KeyCode::Char('r') => {
+                let retried = app.loader.as_mut().map_or(0, |l| l.retry_failed());
+                if retried > 0 {
+                    app.notifications
+                        .info(format!("Retrying {retried} failed page(s)"));
+                } else {
+                    app.notifications.info("No failed pages to retry");
                 }
             }
             _ => {}
@@ -678,34 +1410,382 @@ async fn handle_event(
     }
 }
 
-/// Serves the current spread from cache when available, spawns background fetches for
-/// anything missing, and fire-and-forgets prefetch + protocol pre-building for the next spread.
+/// Handles keys while the Search screen's filter panel has focus.
 ///
-/// # Logic
-/// 1. Immediately serves right/left pages if in `app.page_cache`.
-/// 2. If an image is cached but its protocol is missing or stale (due to resize),
-///    spawns a `spawn_proto` task to re-build it.
-/// 3. For any missing images, spawns a `spawn_fetch` task.
-/// 4. Spawns background fetches for all remaining pages in the chapter.
-async fn load_spread(
+/// Up/Down pick a row, Left/Right cycle its value, digits edit the year,
+/// Enter runs the search (or opens the tag browser on the Tags row) and Tab
+/// returns focus to the search box.
+fn handle_filter_event(
+    client: &MangaDexClient,
+    app: &mut App,
+    key: &KeyEvent,
+    net_tx: &mpsc::Sender<NetMsg>,
+) {
+    let row = FilterRow::ALL[app.filter_cursor];
+    match key.code {
+        KeyCode::Tab => app.filter_panel_open = false,
+        KeyCode::Up => app.filter_cursor = app.filter_cursor.saturating_sub(1),
+        KeyCode::Down if app.filter_cursor + 1 < FilterRow::ALL.len() => {
+            app.filter_cursor += 1;
+        }
+        KeyCode::Left => row.cycle(&mut app.search_filters, false),
+        KeyCode::Right => row.cycle(&mut app.search_filters, true),
+        KeyCode::Char(c) if row == FilterRow::Year && c.is_ascii_digit() => {
+            let digit = c.to_digit(10).unwrap_or(0) as usize;
+            app.search_filters.year = filters::push_year_digit(app.search_filters.year, digit);
+        }
+        KeyCode::Backspace if row == FilterRow::Year => {
+            app.search_filters.year = filters::pop_year_digit(app.search_filters.year);
+        }
+        KeyCode::Enter if row == FilterRow::Tags => {
+            if app.tags.is_empty() {
+                let client = client.clone();
+                start_request(app, RequestKind::Tags, net_tx, async move {
+                    NetReply::Tags(client.tag_client().fetch_tags().await)
+                });
+            } else {
+                app.tag_cursor = 0;
+                app.screen = AppScreen::TagBrowser;
+            }
+        }
+        KeyCode::Enter => {
+            app.filter_panel_open = false;
+            let query = app.pending_query();
+            run_search(client, app, query, 0, net_tx);
+        }
+        _ => {}
+    }
+}
+
+/// Handles keys while the notification history is open.
+fn handle_history_event(app: &mut App, key: &KeyEvent) {
+    match key.code {
+        KeyCode::Esc | HISTORY_KEY => app.history_open = false,
+        KeyCode::Up => app.history_cursor = app.history_cursor.saturating_sub(1),
+        KeyCode::Down if app.history_cursor + 1 < app.notifications.len() => {
+            app.history_cursor += 1;
+        }
+        _ => {}
+    }
+}
+
+/// Handles keys while the chapter language panel has focus.
+///
+/// The feed is refetched when the panel closes, if the language order changed.
+fn handle_language_event(
+    client: &MangaDexClient,
+    app: &mut App,
+    key: &KeyEvent,
+    net_tx: &mpsc::Sender<NetMsg>,
+) {
+    let rows = app.settings.language_rows();
+    let Some(language) = rows.get(app.language_cursor).cloned() else {
+        return;
+    };
+    match key.code {
+        KeyCode::Char('l') | KeyCode::Enter => {
+            app.language_panel_open = false;
+            if app.language_snapshot.as_ref() != Some(&app.settings.languages) {
+                app.save_settings();
+                load_chapters(client, app, net_tx);
+            }
+            app.language_snapshot = None;
+        }
+        KeyCode::Up => app.language_cursor = app.language_cursor.saturating_sub(1),
+        KeyCode::Down if app.language_cursor + 1 < rows.len() => app.language_cursor += 1,
+        KeyCode::Char(' ') => app.settings.toggle_language(&language),
+        KeyCode::Char('K') if app.settings.move_language(&language, true) => {
+            app.language_cursor -= 1;
+        }
+        KeyCode::Char('J') if app.settings.move_language(&language, false) => {
+            app.language_cursor += 1;
+        }
+        _ => {}
+    }
+}
+
+/// Fetches the full details, statistics and cover of `manga` in the
+/// background; the detail screen opens when they arrive.
+///
+/// Statistics and the cover are optional extras: if either request fails the
+/// screen is still shown without them.
+fn open_manga_detail(
+    client: &MangaDexClient,
     app: &mut App,
-    http: &reqwest::Client,
-    img_data: &ImageDataResponse,
-    page_tx: &mpsc::Sender<PageMsg>,
+    manga: MangaData,
+    net_tx: &mpsc::Sender<NetMsg>,
+) {
+    let client = client.clone();
+    start_request(app, RequestKind::MangaDetail, net_tx, async move {
+        let (manga, error) = match client.manga_client().fetch_manga(&manga.id).await {
+            Ok(full) => (full, None),
+            Err(e) => (manga, Some(e)),
+        };
+        let stats = client.manga_client().fetch_statistics(&manga.id).await.ok();
+        let cover = match manga.cover_url() {
+            Some(url) => client.image_client().download_image(&url).await.ok(),
+            None => None,
+        };
+        NetReply::MangaDetail {
+            manga,
+            error,
+            stats,
+            cover,
+        }
+    });
+}
+
+/// Fetches the chapter feed of the selected manga in the background, with
+/// scanlation groups expanded and blocked groups skipped. The chapter list
+/// opens when it arrives.
+fn load_chapters(client: &MangaDexClient, app: &mut App, net_tx: &mpsc::Sender<NetMsg>) {
+    let Some(manga_id) = app.selected_manga.as_ref().map(|manga| manga.id.clone()) else {
+        app.notifications.warn("No manga selected");
+        return;
+    };
+    let blocked = app.settings.blocked_group_ids();
+    let languages = app.settings.languages.clone();
+    let client = client.clone();
+    start_request(app, RequestKind::Chapters, net_tx, async move {
+        let languages: Vec<&str> = languages.iter().map(|l| l.as_str()).collect();
+        let result = client
+            .chapter_client()
+            .with_includes(&[Include::ScanlationGroup])
+            .with_blocked_groups(&blocked)
+            .fetch_chapter(&manga_id, &languages)
+            .await;
+        NetReply::Chapters(result)
+    });
+}
+
+/// Collapses or expands the volume under the chapter list cursor.
+fn toggle_selected_volume(app: &mut App) {
+    let Some(ChapterRow::Volume(v)) = app.chapter_rows().get(app.selected_index).copied() else {
+        return;
+    };
+    let volume = app.volumes[v].volume.clone();
+    if !app.collapsed_volumes.remove(&volume) {
+        app.collapsed_volumes.insert(volume);
+    }
+}
+
+/// The first scanlation group credited for `chapter`, if its attributes were expanded.
+fn chapter_group(chapter: &ChapterData) -> Option<GroupRef> {
+    chapter.scanlation_groups().find_map(|g| {
+        Some(GroupRef {
+            id: g.id.clone(),
+            name: g.as_scanlation_group()?.name.clone(),
+        })
+    })
+}
+
+/// How a page quality mode is named in notifications.
+fn quality_label(mode: QualityMode) -> &'static str {
+    match mode {
+        QualityMode::Original => "original",
+        QualityMode::DataSaver => "data saver",
+        QualityMode::Auto => "automatic",
+    }
+}
+
+/// Reports a failed request, as a warning when waiting or retrying is all
+/// the user can do about it.
+fn notify_error(app: &mut App, context: &str, error: YomuError) {
+    match error {
+        YomuError::RateLimited {
+            retry_after: Some(delay),
+        } => app.notifications.warn(format!(
+            "{context}: MangaDex is busy, try again in {}s",
+            delay.as_secs().max(1)
+        )),
+        YomuError::RateLimited { retry_after: None } => app
+            .notifications
+            .warn(format!("{context}: MangaDex is busy, try again shortly")),
+        YomuError::Timeout { .. } => app
+            .notifications
+            .warn(format!("{context}: MangaDex took too long to respond")),
+        YomuError::NotFound { .. } => app
+            .notifications
+            .warn(format!("{context}: it is no longer on MangaDex")),
+        YomuError::Api { status, errors } => match errors.first() {
+            Some(error) => app.notifications.error(context, error),
+            None => app
+                .notifications
+                .error(context, format!("MangaDex answered {status}")),
+        },
+        error => app.notifications.error(context, error),
+    }
+}
+
+/// Runs a search in the background. Offset 0 replaces the current results;
+/// any other offset appends the next page to them.
+fn run_search(
+    client: &MangaDexClient,
+    app: &mut App,
+    query: SearchQuery,
+    offset: usize,
+    net_tx: &mpsc::Sender<NetMsg>,
+) {
+    let client = client.clone();
+    start_request(app, RequestKind::Search, net_tx, async move {
+        let result = client
+            .search_client()
+            .search_query(&query, offset, SEARCH_PAGE_LIMIT)
+            .await;
+        NetReply::Search {
+            query,
+            append: offset > 0,
+            result,
+        }
+    });
+}
+
+/// Spawns `task` as the pending request, replacing (and aborting) any request
+/// already in flight. Its reply arrives on `net_tx` tagged with a fresh ID.
+fn start_request<F>(app: &mut App, kind: RequestKind, net_tx: &mpsc::Sender<NetMsg>, task: F)
+where
+    F: Future<Output = NetReply> + Send + 'static,
+{
+    app.cancel_request();
+    app.next_request_id += 1;
+    let id = app.next_request_id;
+    let net_tx = net_tx.clone();
+    let handle = tokio::spawn(async move {
+        let reply = task.await;
+        let _ = net_tx.send(NetMsg { id, reply }).await;
+    })
+    .abort_handle();
+    app.pending = Some(PendingRequest {
+        id,
+        kind,
+        started: Instant::now(),
+        handle,
+    });
+}
+
+/// Applies the reply to a finished background request to the app state.
+fn handle_reply(
+    client: &MangaDexClient,
+    app: &mut App,
+    reply: NetReply,
     proto_tx: &mpsc::Sender<ProtoMsg>,
 ) {
+    match reply {
+        NetReply::Search {
+            query,
+            append,
+            result,
+        } => match result {
+            Ok(page) => {
+                app.search_next_offset = page.next_offset();
+                app.search_total = page.total;
+                if append {
+                    app.search_result.extend(page.data);
+                    if app.selected_index + 1 < app.search_result.len() {
+                        app.selected_index += 1;
+                    }
+                } else {
+                    app.search_result = page.data;
+                    app.last_search = Some(query);
+                    app.selected_index = 0;
+                }
+            }
+            Err(e) => notify_error(app, "Search failed", e),
+        },
+        NetReply::Tags(result) => match result {
+            Ok(tags) => {
+                app.tags = tags;
+                app.tag_cursor = 0;
+                app.screen = AppScreen::TagBrowser;
+            }
+            Err(e) => notify_error(app, "Could not load tags", e),
+        },
+        NetReply::MangaDetail {
+            manga,
+            error,
+            stats,
+            cover,
+        } => {
+            if let Some(e) = error {
+                notify_error(app, "Could not load full manga details", e);
+            }
+            app.manga_stats = stats;
+            app.cover = cover;
+            app.cover_proto = None;
+            app.detail_scroll = 0;
+            app.selected_manga = Some(manga);
+            app.screen = AppScreen::MangaDetail;
+        }
+        NetReply::Chapters(result) => {
+            let chapter_data = match result {
+                Ok(chapter_data) => chapter_data,
+                Err(e) => {
+                    notify_error(app, "Could not load chapters", e);
+                    return;
+                }
+            };
+            app.all_chapters = chapter_data;
+            app.refresh_chapters();
+            app.screen = AppScreen::ChapterList;
+        }
+        NetReply::ImageData { chapter_id, result } => {
+            // The chapter may have been filtered out of the list meanwhile.
+            let Some(chapter_index) = app.chapters.iter().position(|c| c.id == chapter_id) else {
+                app.notifications
+                    .warn("Could not open chapter: it is no longer in the chapter list");
+                return;
+            };
+            let image_data = match result {
+                Ok(image_data) => image_data,
+                Err(e) => {
+                    notify_error(app, "Could not open chapter", e);
+                    return;
+                }
+            };
+            if image_data.chapter.data.is_empty() {
+                app.notifications
+                    .warn("This chapter has no pages to display");
+                return;
+            }
+            app.current_chapter = Some(chapter_index);
+            app.close_chapter();
+            app.loader = Some(
+                ChapterLoader::new(client, &chapter_id, image_data)
+                    .with_quality_mode(app.settings.quality_mode),
+            );
+            load_spread(app, proto_tx);
+            app.screen = AppScreen::Reading;
+        }
+    }
+}
+
+/// Serves the current spread from the chapter loader's cache when available,
+/// requests anything missing, and fire-and-forgets prefetch + protocol
+/// pre-building for the next spread.
+///
+/// # Logic
+/// 1. Immediately serves right/left pages if the loader has them cached.
+/// 2. If an image is cached but its protocol is missing or stale (due to resize),
+///    spawns a `spawn_proto` task to re-build it.
+/// 3. Asks the loader to download any missing page of the spread.
+/// 4. Asks the loader to prefetch every remaining page in the chapter.
+fn load_spread(app: &mut App, proto_tx: &mpsc::Sender<ProtoMsg>) {
+    let Some(loader) = app.loader.as_mut() else {
+        return;
+    };
     let current = app.current_page;
     let next = current + 1;
+    loader.set_position(current);
 
     // Right panel (current page)
-    if let Some(img) = app.page_cache.get(&current) {
+    if let Some(img) = loader.page(current) {
         app.page_right = Some(img.clone());
         // Kick off proto build if the cached proto is stale or missing for this area
         if app.last_right_area != Rect::default() {
             let needs_proto = app
                 .proto_cache
                 .get(&(current, false))
-                .map_or(true, |(a, _)| {
+                .is_none_or(|(a, _)| {
                     a.width != app.last_right_area.width || a.height != app.last_right_area.height
                 });
             if needs_proto {
@@ -716,26 +1796,21 @@ async fn load_spread(
                     current,
                     false,
                     proto_tx.clone(),
+                    &mut app.proto_tasks,
                 );
             }
         }
     } else {
         app.page_right = None;
-        spawn_fetch(
-            http.clone(),
-            build_url(img_data, current),
-            current,
-            page_tx.clone(),
-            app.fetch_semaphore.clone(),
-        );
+        loader.request(current);
     }
 
     // Left panel (next page)
-    if next < img_data.chapter.data.len() {
-        if let Some(img) = app.page_cache.get(&next) {
+    if next < loader.page_count() {
+        if let Some(img) = loader.page(next) {
             app.page_left = Some(img.clone());
             if app.last_left_area != Rect::default() {
-                let needs_proto = app.proto_cache.get(&(next, true)).map_or(true, |(a, _)| {
+                let needs_proto = app.proto_cache.get(&(next, true)).is_none_or(|(a, _)| {
                     a.width != app.last_left_area.width || a.height != app.last_left_area.height
                 });
                 if needs_proto {
@@ -746,90 +1821,20 @@ async fn load_spread(
                         next,
                         true,
                         proto_tx.clone(),
+                        &mut app.proto_tasks,
                     );
                 }
             }
         } else {
             app.page_left = None;
-            spawn_fetch(
-                http.clone(),
-                build_url(img_data, next),
-                next,
-                page_tx.clone(),
-                app.fetch_semaphore.clone(),
-            );
+            loader.request(next);
         }
     } else {
         app.page_left = None;
     }
 
     // Download every remaining page in the chapter in the background.
-    // `fetched` tracks which pages already have an in-flight or completed download
-    // so we never duplicate work across multiple navigations.
-    for i in 0..img_data.chapter.data.len() {
-        if !app.page_cache.contains_key(&i) && app.fetched.insert(i) {
-            spawn_fetch(http.clone(), build_url(img_data, i), i, page_tx.clone(), app.fetch_semaphore.clone());
-        }
-    }
-}
-
-fn build_url(img_data: &ImageDataResponse, idx: usize) -> String {
-    format!(
-        "{}/data/{}/{}",
-        img_data.base_url, img_data.chapter.hash, img_data.chapter.data[idx]
-    )
-}
-
-/// Maximum bytes accepted for a single image download (50 MB).
-const MAX_IMAGE_BYTES: u64 = 50 * 1024 * 1024;
-
-/// Downloads an image in a background async task.
-///
-/// Acquires a permit from `sem` before sending the request so at most
-/// `MAX_CONCURRENT_FETCHES` downloads run simultaneously, preventing CDN
-/// rate-limiting. Image decoding is CPU-bound and runs on a blocking thread
-/// via `tokio::task::spawn_blocking`. Sends `None` on any failure so the UI
-/// can display an error instead of a perpetual "Loading…" spinner.
-fn spawn_fetch(
-    http: reqwest::Client,
-    url: String,
-    idx: usize,
-    page_tx: mpsc::Sender<PageMsg>,
-    sem: Arc<Semaphore>,
-) {
-    tokio::spawn(async move {
-        // Hold permit for the lifetime of this download to cap concurrency.
-        let Ok(_permit) = sem.acquire_owned().await else {
-            let _ = page_tx.send((idx, None)).await;
-            return;
-        };
-        let Ok(resp) = http.get(&url).send().await else {
-            let _ = page_tx.send((idx, None)).await;
-            return;
-        };
-        // Reject oversized responses before reading the body.
-        if resp.content_length().map_or(false, |len| len > MAX_IMAGE_BYTES) {
-            let _ = page_tx.send((idx, None)).await;
-            return;
-        }
-        let Ok(bytes) = resp.bytes().await else {
-            let _ = page_tx.send((idx, None)).await;
-            return;
-        };
-        if bytes.len() as u64 > MAX_IMAGE_BYTES {
-            let _ = page_tx.send((idx, None)).await;
-            return;
-        }
-        // Decode on a blocking thread — image::load_from_memory is CPU-intensive.
-        let bytes = bytes.to_vec();
-        let Ok(Ok(img)) =
-            tokio::task::spawn_blocking(move || image::load_from_memory(&bytes)).await
-        else {
-            let _ = page_tx.send((idx, None)).await;
-            return;
-        };
-        let _ = page_tx.send((idx, Some(img))).await;
-    });
+    loader.prefetch_all();
 }
 
 /// Encodes a `DynamicImage` into a terminal graphics protocol in a background task.
@@ -844,6 +1849,7 @@ fn spawn_fetch(
 /// * `idx` - The page index.
 /// * `is_left` - Whether this is for the left or right panel.
 /// * `proto_tx` - Channel to send the result back to the main loop.
+/// * `tasks` - The open chapter's tasks; the result is tagged with their generation.
 fn spawn_proto(
     picker: Option<ratatui_image::picker::Picker>,
     img: image::DynamicImage,
@@ -851,9 +1857,11 @@ fn spawn_proto(
     idx: usize,
     is_left: bool,
     proto_tx: mpsc::Sender<ProtoMsg>,
+    tasks: &mut ProtoTasks,
 ) {
+    let generation = tasks.generation;
     let picker = picker.unwrap_or_else(ratatui_image::picker::Picker::halfblocks);
-    tokio::spawn(async move {
+    tasks.set.spawn(async move {
         let Ok(Ok(p)) = tokio::task::spawn_blocking(move || {
             picker.new_protocol(
                 img,
@@ -865,6 +1873,6 @@ fn spawn_proto(
         else {
             return;
         };
-        let _ = proto_tx.send((idx, is_left, p)).await;
+        let _ = proto_tx.send((generation, idx, is_left, p)).await;
     });
 }
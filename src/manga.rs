@@ -0,0 +1,208 @@
+use crate::client::MangaDexClient;
+use crate::error::{Result, YomuError};
+use crate::ratelimit::Endpoint;
+use crate::relationship::{Include, RelationshipKind, include_pairs};
+use crate::search::MangaData;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Base URL for cover art served by MangaDex.
+const COVER_BASE_URL: &str = "https://uploads.mangadex.org/covers";
+
+/// A client for fetching details about a single manga.
+#[derive(Clone)]
+pub struct MangaClient {
+    /// The parent `MangaDexClient` (a cheap clone sharing its connection pool).
+    pub client: MangaDexClient,
+    /// Related entities to expand inline in every response.
+    pub includes: Vec<Include>,
+}
+
+impl MangaClient {
+    /// Requests that the given related entities be expanded inline, so their
+    /// attributes are available on each result's `relationships`.
+    pub fn with_includes(mut self, includes: &[Include]) -> Self {
+        self.includes.extend_from_slice(includes);
+        self
+    }
+
+    /// Fetches a manga by ID with its cover art, authors and artists expanded.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use yomu::MangaDexClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = MangaDexClient::new()?;
+    /// let manga = client
+    ///     .manga_client()
+    ///     .fetch_manga("a77742b1-befd-49a4-bff5-1ad4e6b0ef7b")
+    ///     .await?;
+    /// println!("Authors: {:?}", manga.author_names());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_manga(&self, manga_id: &str) -> Result<MangaData> {
+        let mut includes = vec![Include::CoverArt, Include::Author, Include::Artist];
+        includes.extend_from_slice(&self.includes);
+        let request = self
+            .client
+            .http_client()
+            .get(format!("{}/manga/{}", self.client.base_url(), manga_id))
+            .query(&include_pairs(&includes));
+        let resp = self.client.send(Endpoint::Api, request).await?;
+        Ok(resp.json::<MangaResponse>().await?.data)
+    }
+
+    /// Fetches rating and follow statistics for a manga.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use yomu::MangaDexClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = MangaDexClient::new()?;
+    /// let stats = client
+    ///     .manga_client()
+    ///     .fetch_statistics("a77742b1-befd-49a4-bff5-1ad4e6b0ef7b")
+    ///     .await?;
+    /// println!("{:?} follows", stats.follows);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_statistics(&self, manga_id: &str) -> Result<MangaStatistics> {
+        let url = format!("{}/statistics/manga/{}", self.client.base_url(), manga_id);
+        let request = self.client.http_client().get(&url);
+        let resp = self.client.send(Endpoint::Api, request).await?;
+        let mut resp_json = resp.json::<StatisticsResponse>().await?;
+        resp_json
+            .statistics
+            .remove(manga_id)
+            .ok_or(YomuError::NotFound { url })
+    }
+}
+
+impl MangaData {
+    /// Returns the manga's title in the first of `languages` it has one in.
+    ///
+    /// Each language is looked up in the main title and then in `altTitles`.
+    /// If none match, any available title is returned, so a manga is only
+    /// untitled when MangaDex has no title for it at all.
+    pub fn localized_title(&self, languages: &[&str]) -> Option<&str> {
+        let titles = self.attributes.title.as_ref();
+        let alt_titles = &self.attributes.alt_titles;
+        languages
+            .iter()
+            .find_map(|language| {
+                titles
+                    .and_then(|t| t.get(*language))
+                    .or_else(|| alt_titles.iter().find_map(|alt| alt.get(*language)))
+            })
+            .or_else(|| titles.and_then(|t| t.values().next()))
+            .or_else(|| alt_titles.iter().find_map(|alt| alt.values().next()))
+            .map(|title| title.as_str())
+    }
+
+    /// Returns the URL of a 256px-wide thumbnail of the manga's cover, if
+    /// the cover art relationship was expanded.
+    pub fn cover_url(&self) -> Option<String> {
+        let cover = self.relationships.iter().find_map(|r| r.as_cover_art())?;
+        Some(format!(
+            "{COVER_BASE_URL}/{}/{}.256.jpg",
+            self.id, cover.file_name
+        ))
+    }
+
+    /// Returns the names of the manga's authors, if expanded.
+    pub fn author_names(&self) -> Vec<&str> {
+        self.related_names(RelationshipKind::Author)
+    }
+
+    /// Returns the names of the manga's artists, if expanded.
+    pub fn artist_names(&self) -> Vec<&str> {
+        self.related_names(RelationshipKind::Artist)
+    }
+
+    fn related_names(&self, kind: RelationshipKind) -> Vec<&str> {
+        self.relationships
+            .iter()
+            .filter(|r| r.kind == kind)
+            .filter_map(|r| r.as_author())
+            .map(|a| a.name.as_str())
+            .collect()
+    }
+}
+
+/// Response from the MangaDex API for a single manga.
+#[derive(Deserialize)]
+pub struct MangaResponse {
+    /// Result status of the request.
+    pub result: String,
+    /// Type of the response.
+    pub response: String,
+    /// The manga.
+    pub data: MangaData,
+}
+
+/// Response from the MangaDex API for a statistics request.
+#[derive(Deserialize)]
+pub struct StatisticsResponse {
+    /// Result status of the request.
+    pub result: String,
+    /// Statistics keyed by manga ID.
+    pub statistics: HashMap<String, MangaStatistics>,
+}
+
+/// Reader statistics for a manga.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct MangaStatistics {
+    /// Rating information.
+    pub rating: MangaRating,
+    /// Number of users following the manga.
+    pub follows: Option<usize>,
+}
+
+/// Rating information for a manga.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct MangaRating {
+    /// Plain average of user ratings (out of 10).
+    pub average: Option<f64>,
+    /// Bayesian average of user ratings (out of 10).
+    pub bayesian: Option<f64>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn localized_title_follows_the_preference_chain() {
+        let manga: MangaData = serde_json::from_value(serde_json::json!({
+            "id": "m",
+            "type": "manga",
+            "attributes": {
+                "title": { "ko": "나 혼자만 레벨업" },
+                "altTitles": [
+                    { "ja-ro": "Ore dake Level Up na Ken" },
+                    { "en": "Solo Leveling" }
+                ],
+                "description": null,
+                "publicationDemographic": null,
+                "status": null,
+                "year": null
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            manga.localized_title(&["en", "ja-ro"]),
+            Some("Solo Leveling")
+        );
+        assert_eq!(
+            manga.localized_title(&["ja-ro", "en"]),
+            Some("Ore dake Level Up na Ken")
+        );
+        assert_eq!(manga.localized_title(&["fr"]), Some("나 혼자만 레벨업"));
+    }
+}
@@ -0,0 +1,106 @@
+use ratatui::style::Color;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// How long a notification stays on screen as a toast.
+pub const TOAST_DURATION: Duration = Duration::from_secs(5);
+/// Maximum number of toasts shown at once; older ones stay in the history.
+pub const MAX_TOASTS: usize = 3;
+/// Maximum number of notifications kept in the history.
+const MAX_HISTORY: usize = 100;
+
+/// How serious a notification is, which decides its colour.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// The colour toasts and history entries of this severity are drawn in.
+    pub fn color(&self) -> Color {
+        match self {
+            Severity::Info => Color::Rgb(120, 200, 255),
+            Severity::Warning => Color::Rgb(255, 200, 120),
+            Severity::Error => Color::Rgb(255, 95, 95),
+        }
+    }
+
+    /// Short label shown before the message.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+/// A single message shown to the user.
+pub struct Notification {
+    /// How serious the message is.
+    pub severity: Severity,
+    /// The message text.
+    pub message: String,
+    /// When the message was raised.
+    pub created: Instant,
+}
+
+/// A queue of timed messages: recent ones are shown as toasts, and the last
+/// [`MAX_HISTORY`] are kept for the error history view.
+#[derive(Default)]
+pub struct Notifications {
+    history: VecDeque<Notification>,
+}
+
+impl Notifications {
+    /// Queues a message, dropping the oldest one if the history is full.
+    pub fn push(&mut self, severity: Severity, message: impl Into<String>) {
+        if self.history.len() == MAX_HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back(Notification {
+            severity,
+            message: message.into(),
+            created: Instant::now(),
+        });
+    }
+
+    /// Queues an informational message.
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.push(Severity::Info, message);
+    }
+
+    /// Queues a warning.
+    pub fn warn(&mut self, message: impl Into<String>) {
+        self.push(Severity::Warning, message);
+    }
+
+    /// Queues an error, prefixed with what was being attempted.
+    pub fn error(&mut self, context: &str, error: impl std::fmt::Display) {
+        self.push(Severity::Error, format!("{context}: {error}"));
+    }
+
+    /// Toasts still on screen, oldest first.
+    pub fn toasts(&self) -> impl Iterator<Item = &Notification> {
+        let active: Vec<&Notification> = self
+            .history
+            .iter()
+            .rev()
+            .take_while(|n| n.created.elapsed() < TOAST_DURATION)
+            .take(MAX_TOASTS)
+            .collect();
+        active.into_iter().rev()
+    }
+
+    /// Every kept notification, newest first.
+    pub fn history(&self) -> impl Iterator<Item = &Notification> {
+        self.history.iter().rev()
+    }
+
+    /// Number of kept notifications.
+    pub fn len(&self) -> usize {
+        self.history.len()
+    }
+}
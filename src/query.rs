@@ -0,0 +1,382 @@
+/// Publication status of a manga.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MangaStatus {
+    Ongoing,
+    Completed,
+    Hiatus,
+    Cancelled,
+}
+
+impl MangaStatus {
+    /// Every status, in the order MangaDex documents them.
+    pub const ALL: [MangaStatus; 4] = [
+        MangaStatus::Ongoing,
+        MangaStatus::Completed,
+        MangaStatus::Hiatus,
+        MangaStatus::Cancelled,
+    ];
+
+    /// The value MangaDex uses for this status.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MangaStatus::Ongoing => "ongoing",
+            MangaStatus::Completed => "completed",
+            MangaStatus::Hiatus => "hiatus",
+            MangaStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// Targeted publication demographic of a manga.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Demographic {
+    Shounen,
+    Shoujo,
+    Seinen,
+    Josei,
+    /// Manga with no demographic set.
+    None,
+}
+
+impl Demographic {
+    /// Every demographic, in the order MangaDex documents them.
+    pub const ALL: [Demographic; 5] = [
+        Demographic::Shounen,
+        Demographic::Shoujo,
+        Demographic::Seinen,
+        Demographic::Josei,
+        Demographic::None,
+    ];
+
+    /// The value MangaDex uses for this demographic.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Demographic::Shounen => "shounen",
+            Demographic::Shoujo => "shoujo",
+            Demographic::Seinen => "seinen",
+            Demographic::Josei => "josei",
+            Demographic::None => "none",
+        }
+    }
+}
+
+/// Content rating of a manga.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentRating {
+    Safe,
+    Suggestive,
+    Erotica,
+    Pornographic,
+}
+
+impl ContentRating {
+    /// Every content rating, from mildest to most explicit.
+    pub const ALL: [ContentRating; 4] = [
+        ContentRating::Safe,
+        ContentRating::Suggestive,
+        ContentRating::Erotica,
+        ContentRating::Pornographic,
+    ];
+
+    /// The value MangaDex uses for this rating.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContentRating::Safe => "safe",
+            ContentRating::Suggestive => "suggestive",
+            ContentRating::Erotica => "erotica",
+            ContentRating::Pornographic => "pornographic",
+        }
+    }
+}
+
+/// How multiple tags in an include or exclude list are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagMode {
+    /// A manga must match every tag.
+    And,
+    /// A manga must match at least one tag.
+    Or,
+}
+
+impl TagMode {
+    /// The value MangaDex uses for this mode.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TagMode::And => "AND",
+            TagMode::Or => "OR",
+        }
+    }
+}
+
+/// A field search results can be sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderField {
+    Relevance,
+    LatestUploadedChapter,
+    FollowedCount,
+    Rating,
+    CreatedAt,
+    UpdatedAt,
+    Title,
+    Year,
+}
+
+impl OrderField {
+    /// Every sortable field.
+    pub const ALL: [OrderField; 8] = [
+        OrderField::Relevance,
+        OrderField::LatestUploadedChapter,
+        OrderField::FollowedCount,
+        OrderField::Rating,
+        OrderField::CreatedAt,
+        OrderField::UpdatedAt,
+        OrderField::Title,
+        OrderField::Year,
+    ];
+
+    /// The key MangaDex uses inside `order[...]` for this field.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderField::Relevance => "relevance",
+            OrderField::LatestUploadedChapter => "latestUploadedChapter",
+            OrderField::FollowedCount => "followedCount",
+            OrderField::Rating => "rating",
+            OrderField::CreatedAt => "createdAt",
+            OrderField::UpdatedAt => "updatedAt",
+            OrderField::Title => "title",
+            OrderField::Year => "year",
+        }
+    }
+}
+
+/// Sort direction for an [`OrderField`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+impl SortDirection {
+    /// The value MangaDex uses for this direction.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortDirection::Ascending => "asc",
+            SortDirection::Descending => "desc",
+        }
+    }
+}
+
+/// A structured manga search, built up with chained setters.
+///
+/// An empty query matches every manga. Fields left empty are not sent.
+///
+/// # Example
+///
+/// ```rust
+/// use yomu::query::{MangaStatus, OrderField, SearchQuery, SortDirection};
+///
+/// let query = SearchQuery::new()
+///     .status(MangaStatus::Completed)
+///     .original_language("ko")
+///     .order(OrderField::FollowedCount, SortDirection::Descending);
+/// assert!(query.query_pairs().contains(&("status[]".into(), "completed".into())));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    /// Title (or partial title) to match.
+    pub title: Option<String>,
+    /// Tag IDs a manga must have.
+    pub included_tags: Vec<String>,
+    /// How `included_tags` are combined. The API defaults to `AND`.
+    pub included_tags_mode: Option<TagMode>,
+    /// Tag IDs a manga must not have.
+    pub excluded_tags: Vec<String>,
+    /// How `excluded_tags` are combined. The API defaults to `OR`.
+    pub excluded_tags_mode: Option<TagMode>,
+    /// Accepted publication statuses.
+    pub status: Vec<MangaStatus>,
+    /// Accepted publication demographics.
+    pub publication_demographic: Vec<Demographic>,
+    /// Accepted content ratings. The API applies its own default when empty.
+    pub content_rating: Vec<ContentRating>,
+    /// Year of release.
+    pub year: Option<usize>,
+    /// Accepted original languages (e.g. `ja`, `ko`).
+    pub original_language: Vec<String>,
+    /// Sort keys, applied in order.
+    pub order: Vec<(OrderField, SortDirection)>,
+}
+
+impl SearchQuery {
+    /// Creates an empty query.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the title to search for.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Requires a tag by ID.
+    pub fn include_tag(mut self, tag_id: impl Into<String>) -> Self {
+        self.included_tags.push(tag_id.into());
+        self
+    }
+
+    /// Sets how included tags are combined.
+    pub fn included_tags_mode(mut self, mode: TagMode) -> Self {
+        self.included_tags_mode = Some(mode);
+        self
+    }
+
+    /// Excludes a tag by ID.
+    pub fn exclude_tag(mut self, tag_id: impl Into<String>) -> Self {
+        self.excluded_tags.push(tag_id.into());
+        self
+    }
+
+    /// Sets how excluded tags are combined.
+    pub fn excluded_tags_mode(mut self, mode: TagMode) -> Self {
+        self.excluded_tags_mode = Some(mode);
+        self
+    }
+
+    /// Adds an accepted publication status.
+    pub fn status(mut self, status: MangaStatus) -> Self {
+        self.status.push(status);
+        self
+    }
+
+    /// Adds an accepted publication demographic.
+    pub fn publication_demographic(mut self, demographic: Demographic) -> Self {
+        self.publication_demographic.push(demographic);
+        self
+    }
+
+    /// Adds an accepted content rating.
+    pub fn content_rating(mut self, rating: ContentRating) -> Self {
+        self.content_rating.push(rating);
+        self
+    }
+
+    /// Restricts results to a year of release.
+    pub fn year(mut self, year: usize) -> Self {
+        self.year = Some(year);
+        self
+    }
+
+    /// Adds an accepted original language.
+    pub fn original_language(mut self, language: impl Into<String>) -> Self {
+        self.original_language.push(language.into());
+        self
+    }
+
+    /// Appends a sort key.
+    pub fn order(mut self, field: OrderField, direction: SortDirection) -> Self {
+        self.order.push((field, direction));
+        self
+    }
+
+    /// Returns `true` if any filter other than the title is set.
+    pub fn has_filters(&self) -> bool {
+        let title_only = SearchQuery {
+            title: self.title.clone(),
+            ..SearchQuery::default()
+        };
+        *self != title_only
+    }
+
+    /// Converts the query into the `(key, value)` pairs expected by `/manga`.
+    pub fn query_pairs(&self) -> Vec<(String, String)> {
+        let mut pairs: Vec<(String, String)> = Vec::new();
+        if let Some(title) = &self.title {
+            pairs.push(("title".into(), title.clone()));
+        }
+        for tag in &self.included_tags {
+            pairs.push(("includedTags[]".into(), tag.clone()));
+        }
+        if let Some(mode) = self.included_tags_mode {
+            pairs.push(("includedTagsMode".into(), mode.as_str().into()));
+        }
+        for tag in &self.excluded_tags {
+            pairs.push(("excludedTags[]".into(), tag.clone()));
+        }
+        if let Some(mode) = self.excluded_tags_mode {
+            pairs.push(("excludedTagsMode".into(), mode.as_str().into()));
+        }
+        for status in &self.status {
+            pairs.push(("status[]".into(), status.as_str().into()));
+        }
+        for demographic in &self.publication_demographic {
+            pairs.push((
+                "publicationDemographic[]".into(),
+                demographic.as_str().into(),
+            ));
+        }
+        for rating in &self.content_rating {
+            pairs.push(("contentRating[]".into(), rating.as_str().into()));
+        }
+        if let Some(year) = self.year {
+            pairs.push(("year".into(), year.to_string()));
+        }
+        for language in &self.original_language {
+            pairs.push(("originalLanguage[]".into(), language.clone()));
+        }
+        for (field, direction) in &self.order {
+            pairs.push((
+                format!("order[{}]", field.as_str()),
+                direction.as_str().into(),
+            ));
+        }
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn empty_query_sends_nothing() {
+        assert!(SearchQuery::new().query_pairs().is_empty());
+        assert!(!SearchQuery::new().title("berserk").has_filters());
+    }
+
+    #[test]
+    fn query_pairs_use_mangadex_keys() {
+        let query = SearchQuery::new()
+            .title("berserk")
+            .include_tag("tag-a")
+            .exclude_tag("tag-b")
+            .status(MangaStatus::Ongoing)
+            .status(MangaStatus::Hiatus)
+            .publication_demographic(Demographic::Seinen)
+            .content_rating(ContentRating::Safe)
+            .year(1989)
+            .original_language("ja")
+            .order(OrderField::FollowedCount, SortDirection::Descending);
+        assert!(query.has_filters());
+        let pairs = query.query_pairs();
+        let expected = [
+            ("title", "berserk"),
+            ("includedTags[]", "tag-a"),
+            ("excludedTags[]", "tag-b"),
+            ("status[]", "ongoing"),
+            ("status[]", "hiatus"),
+            ("publicationDemographic[]", "seinen"),
+            ("contentRating[]", "safe"),
+            ("year", "1989"),
+            ("originalLanguage[]", "ja"),
+            ("order[followedCount]", "desc"),
+        ];
+        for (key, value) in expected {
+            assert!(
+                pairs.contains(&(key.to_string(), value.to_string())),
+                "missing {key}={value}"
+            );
+        }
+        assert_eq!(pairs.len(), expected.len());
+    }
+}
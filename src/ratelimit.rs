@@ -0,0 +1,286 @@
+use reqwest::header::HeaderMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// A request budget: at most `requests` per `period`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quota {
+    /// Requests allowed per period; also the largest burst.
+    pub requests: u32,
+    /// Length of the period.
+    pub period: Duration,
+}
+
+impl Quota {
+    /// `requests` per second.
+    pub const fn per_second(requests: u32) -> Self {
+        Quota {
+            requests,
+            period: Duration::from_secs(1),
+        }
+    }
+
+    /// `requests` per minute.
+    pub const fn per_minute(requests: u32) -> Self {
+        Quota {
+            requests,
+            period: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Request budgets for the MangaDex API, set with
+/// [`MangaDexClientBuilder::rate_limits`](crate::client::MangaDexClientBuilder::rate_limits).
+///
+/// The defaults match the limits MangaDex documents: about 5 requests per
+/// second per IP, and 40 per minute on `/at-home/server`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimits {
+    /// Budget shared by every API request.
+    pub global: Quota,
+    /// Extra budget for `/at-home/server/{id}` requests.
+    pub at_home: Quota,
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        RateLimits {
+            global: Quota::per_second(5),
+            at_home: Quota::per_minute(40),
+        }
+    }
+}
+
+/// The budget an API request is charged to, besides the global one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Endpoint {
+    /// Any endpoint without a budget of its own.
+    Api,
+    /// `/at-home/server/{id}`.
+    AtHome,
+}
+
+/// A token bucket that hands out reservations instead of rejecting requests.
+///
+/// Tokens may go negative: each caller takes one and waits until the bucket
+/// would have refilled to it, so concurrent callers queue up in order.
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+    /// Set when the server says the budget is spent.
+    paused_until: Option<Instant>,
+}
+
+impl TokenBucket {
+    fn new(quota: Quota, now: Instant) -> Self {
+        let capacity = f64::from(quota.requests.max(1));
+        TokenBucket {
+            capacity,
+            tokens: capacity,
+            refill_per_sec: capacity / quota.period.as_secs_f64().max(f64::EPSILON),
+            last_refill: now,
+            paused_until: None,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens =
+            (self.tokens + elapsed.as_secs_f64() * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    /// Takes a token and returns how long to wait before using it.
+    fn reserve(&mut self, now: Instant) -> Duration {
+        self.refill(now);
+        self.tokens -= 1.0;
+        let refill_wait = if self.tokens < 0.0 {
+            Duration::from_secs_f64(-self.tokens / self.refill_per_sec)
+        } else {
+            Duration::ZERO
+        };
+        let pause_wait = self
+            .paused_until
+            .map_or(Duration::ZERO, |until| until.saturating_duration_since(now));
+        refill_wait.max(pause_wait)
+    }
+
+    /// Applies the server's view of the remaining budget.
+    fn observe(&mut self, remaining: Option<u32>, retry_at: Option<Instant>, now: Instant) {
+        self.refill(now);
+        if let Some(remaining) = remaining {
+            self.tokens = self.tokens.min(f64::from(remaining));
+        }
+        if let Some(until) = retry_at
+            && remaining.is_none_or(|r| r == 0)
+            && until > now
+        {
+            self.paused_until = Some(self.paused_until.map_or(until, |p| p.max(until)));
+        }
+    }
+}
+
+/// Throttles API requests to stay inside MangaDex's rate limits.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    buckets: Mutex<Buckets>,
+}
+
+#[derive(Debug)]
+struct Buckets {
+    global: TokenBucket,
+    at_home: TokenBucket,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(limits: RateLimits) -> Self {
+        let now = Instant::now();
+        RateLimiter {
+            buckets: Mutex::new(Buckets {
+                global: TokenBucket::new(limits.global, now),
+                at_home: TokenBucket::new(limits.at_home, now),
+            }),
+        }
+    }
+
+    /// Waits until a request to `endpoint` fits in every budget it is charged to.
+    pub(crate) async fn acquire(&self, endpoint: Endpoint) {
+        let wait = self.reserve(endpoint, Instant::now());
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    fn reserve(&self, endpoint: Endpoint, now: Instant) -> Duration {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let global = buckets.global.reserve(now);
+        match endpoint {
+            Endpoint::Api => global,
+            Endpoint::AtHome => global.max(buckets.at_home.reserve(now)),
+        }
+    }
+
+    /// Reads the `X-RateLimit-*` headers of a response to `endpoint`.
+    ///
+    /// MangaDex reports the budget left for the route, and once it is spent,
+    /// the Unix time at which requests are accepted again. Those numbers only
+    /// apply to the endpoint's own bucket: the global bucket follows the
+    /// configured quota, so one exhausted route cannot stall every other
+    /// request. Routes without a bucket of their own rely on `429` handling.
+    pub(crate) fn observe(&self, endpoint: Endpoint, headers: &HeaderMap) {
+        if endpoint == Endpoint::Api {
+            return;
+        }
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok())
+        };
+        let remaining = header("x-ratelimit-remaining").map(|r| r.min(u64::from(u32::MAX)) as u32);
+        let retry_at = header("x-ratelimit-retry-after").map(unix_to_instant);
+        if remaining.is_none() && retry_at.is_none() {
+            return;
+        }
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        buckets.at_home.observe(remaining, retry_at, now);
+    }
+}
+
+/// How long a `429` response asks the client to wait: the standard
+/// `Retry-After` header in seconds, or MangaDex's `X-RateLimit-Retry-After`
+/// Unix time.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok())
+    };
+    header("retry-after")
+        .map(Duration::from_secs)
+        .or_else(|| header("x-ratelimit-retry-after").map(until_unix))
+}
+
+/// Converts a Unix timestamp in seconds to an `Instant`, clamped to now.
+fn unix_to_instant(secs: u64) -> Instant {
+    Instant::now() + until_unix(secs)
+}
+
+/// Time left until a Unix timestamp in seconds, or zero if it has passed.
+fn until_unix(secs: u64) -> Duration {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    Duration::from_secs(secs).saturating_sub(now)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bucket_allows_a_burst_then_spaces_requests() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(Quota::per_second(5), start);
+        for _ in 0..5 {
+            assert_eq!(bucket.reserve(start), Duration::ZERO);
+        }
+        let wait = bucket.reserve(start);
+        assert!((wait.as_secs_f64() - 0.2).abs() < 1e-6);
+        let wait = bucket.reserve(start);
+        assert!((wait.as_secs_f64() - 0.4).abs() < 1e-6);
+        // A second later the queued requests have been paid back.
+        let later = start + Duration::from_secs(1);
+        assert_eq!(bucket.reserve(later), Duration::ZERO);
+    }
+
+    #[test]
+    fn server_headers_pause_the_bucket() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(Quota::per_minute(40), start);
+        bucket.observe(Some(2), None, start);
+        assert_eq!(bucket.reserve(start), Duration::ZERO);
+        assert_eq!(bucket.reserve(start), Duration::ZERO);
+        assert!(bucket.reserve(start) > Duration::ZERO);
+
+        let mut bucket = TokenBucket::new(Quota::per_minute(40), start);
+        bucket.observe(Some(0), Some(start + Duration::from_secs(30)), start);
+        assert_eq!(bucket.reserve(start), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn route_headers_only_pause_their_own_bucket() {
+        let retry_at = SystemTime::now() + Duration::from_secs(60);
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", "0".parse().unwrap());
+        headers.insert(
+            "x-ratelimit-retry-after",
+            retry_at
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+                .to_string()
+                .parse()
+                .unwrap(),
+        );
+
+        let limiter = RateLimiter::new(RateLimits::default());
+        limiter.observe(Endpoint::Api, &headers);
+        assert_eq!(
+            limiter.reserve(Endpoint::Api, Instant::now()),
+            Duration::ZERO
+        );
+
+        limiter.observe(Endpoint::AtHome, &headers);
+        assert!(limiter.reserve(Endpoint::AtHome, Instant::now()) > Duration::from_secs(50));
+        assert_eq!(
+            limiter.reserve(Endpoint::Api, Instant::now()),
+            Duration::ZERO
+        );
+    }
+}
@@ -0,0 +1,772 @@
+use crate::client::MangaDexClient;
+use crate::error::{Result, YomuError};
+use crate::image::{
+    ImageAttributes, ImageClient, ImageDataResponse, ImageQuality, UPLOADS_BASE_URL,
+};
+use image::DynamicImage;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use tokio::sync::{Semaphore, mpsc};
+use tokio::task::JoinSet;
+
+/// Default maximum number of concurrent page downloads.
+pub const MAX_CONCURRENT_FETCHES: usize = 8;
+/// Default maximum number of decoded pages kept in memory.
+pub const MAX_CACHE_PAGES: usize = 20;
+/// In [`QualityMode::Auto`], switch to data saver when downloads are slower
+/// than this many bytes per second...
+pub const AUTO_SLOW_BYTES_PER_SEC: f64 = 200.0 * 1024.0;
+/// ...and back to originals once they are faster than this.
+pub const AUTO_FAST_BYTES_PER_SEC: f64 = 600.0 * 1024.0;
+/// Downloads that finished this long before the newest one no longer count
+/// towards the measured throughput.
+const THROUGHPUT_WINDOW: Duration = Duration::from_secs(10);
+
+/// Something that happened to a page of a [`ChapterLoader`].
+#[derive(Debug)]
+pub enum PageEvent {
+    /// The page finished downloading and is now in the cache. `evicted` is
+    /// the page dropped from the cache to make room, if any.
+    Loaded {
+        index: usize,
+        evicted: Option<usize>,
+    },
+    /// The page could not be downloaded or decoded.
+    Failed { index: usize, error: YomuError },
+    /// Downloads failed on the current server, so the loader moved to
+    /// another one; pages in flight are retried there.
+    ServerChanged { base_url: String, kind: ServerKind },
+}
+
+/// Which server a [`ChapterLoader`] downloads pages from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerKind {
+    /// The @Home node the chapter was assigned.
+    Assigned,
+    /// A new @Home node requested after the assigned one failed.
+    Reassigned,
+    /// MangaDex's own server, [`UPLOADS_BASE_URL`], used as a last resort.
+    Upstream,
+}
+
+/// How a [`ChapterLoader`] picks the quality of the pages it downloads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum QualityMode {
+    /// Always download the pages as uploaded.
+    #[default]
+    Original,
+    /// Always download compressed data-saver pages.
+    DataSaver,
+    /// Download originals, but drop to data saver while downloads are slow.
+    Auto,
+}
+
+impl QualityMode {
+    /// Every mode, in the order a UI should cycle through them.
+    pub const ALL: [QualityMode; 3] = [
+        QualityMode::Original,
+        QualityMode::DataSaver,
+        QualityMode::Auto,
+    ];
+}
+
+/// The quality mode of a loader and the download speed it has measured.
+struct Quality {
+    mode: QualityMode,
+    /// What [`QualityMode::Auto`] currently resolves to.
+    auto: ImageQuality,
+    /// Recent downloads as (start, finish, bytes), oldest first.
+    downloads: VecDeque<(Instant, Instant, usize)>,
+    /// Download speed over [`THROUGHPUT_WINDOW`] in bytes per second.
+    throughput: Option<f64>,
+}
+
+impl Quality {
+    fn new() -> Self {
+        Quality {
+            mode: QualityMode::Original,
+            auto: ImageQuality::Original,
+            downloads: VecDeque::new(),
+            throughput: None,
+        }
+    }
+
+    fn current(&self) -> ImageQuality {
+        match self.mode {
+            QualityMode::Original => ImageQuality::Original,
+            QualityMode::DataSaver => ImageQuality::DataSaver,
+            QualityMode::Auto => self.auto,
+        }
+    }
+
+    /// Adds one download to the throughput estimate.
+    ///
+    /// Downloads run concurrently and share the connection, so the estimate
+    /// is the bytes of every recent download over the time any of them was
+    /// in progress, not an average of each download's own speed.
+    fn record(&mut self, bytes: usize, started: Instant, finished: Instant) {
+        self.downloads.push_back((started, finished, bytes));
+        while let Some(&(_, oldest, _)) = self.downloads.front()
+            && oldest + THROUGHPUT_WINDOW < finished
+        {
+            self.downloads.pop_front();
+        }
+
+        let mut spans: Vec<(Instant, Instant)> =
+            self.downloads.iter().map(|&(s, f, _)| (s, f)).collect();
+        spans.sort_unstable();
+        let mut busy = Duration::ZERO;
+        let mut current: Option<(Instant, Instant)> = None;
+        for (start, end) in spans {
+            current = match current {
+                Some((s, e)) if start <= e => Some((s, e.max(end))),
+                Some((s, e)) => {
+                    busy += e - s;
+                    Some((start, end))
+                }
+                None => Some((start, end)),
+            };
+        }
+        if let Some((s, e)) = current {
+            busy += e - s;
+        }
+        let total: usize = self.downloads.iter().map(|&(_, _, b)| b).sum();
+        let throughput = total as f64 / busy.as_secs_f64().max(0.001);
+        self.throughput = Some(throughput);
+        // Two thresholds so the mode does not flap around a single one.
+        if throughput < AUTO_SLOW_BYTES_PER_SEC {
+            self.auto = ImageQuality::DataSaver;
+        } else if throughput > AUTO_FAST_BYTES_PER_SEC {
+            self.auto = ImageQuality::Original;
+        }
+    }
+}
+
+/// A message from a download task to its loader.
+enum TaskMsg {
+    Page(usize, Result<DynamicImage>),
+    Server(String, ServerKind),
+}
+
+/// The server pages are downloaded from.
+struct Node {
+    base_url: String,
+    kind: ServerKind,
+    /// Bumped on every switch, so a task can tell whether the failure it saw
+    /// has already been dealt with.
+    epoch: u64,
+}
+
+/// State shared between a loader and its download tasks.
+struct Shared {
+    images: ImageClient,
+    chapter_id: String,
+    files: ImageAttributes,
+    node: Mutex<Node>,
+    quality: Mutex<Quality>,
+    /// Serialises failovers so a burst of failures switches servers once.
+    failover: tokio::sync::Mutex<()>,
+}
+
+/// Downloads, decodes and caches the pages of one chapter in the background.
+///
+/// Downloads run on tokio tasks, at most [`MAX_CONCURRENT_FETCHES`] at a
+/// time, and images are decoded on blocking threads. Results arrive as
+/// [`PageEvent`]s from [`next_event`](Self::next_event). The cache keeps at
+/// most [`MAX_CACHE_PAGES`] pages, evicting those farthest from the reading
+/// position. Dropping the loader aborts every download still in flight.
+///
+/// When a page still fails with a transient error after the client's
+/// retries, or does not match the SHA-256 in its filename, the loader asks
+/// the API for a new @Home node, and if that fails too, falls back to
+/// [`UPLOADS_BASE_URL`]. Each switch is reported as a
+/// [`PageEvent::ServerChanged`].
+///
+/// Pages are downloaded in the quality chosen by the [`QualityMode`], which
+/// can be changed while the chapter loads; pages already cached are kept.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use yomu::MangaDexClient;
+/// use yomu::reader::{ChapterLoader, PageEvent};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = MangaDexClient::new()?;
+/// let chapter_id = "73af4d8d-1532-4a72-b1b9-8f4e5cd295c9";
+/// let image_data = client.image_client().fetch_image_data(chapter_id).await?;
+/// let mut loader = ChapterLoader::new(&client, chapter_id, image_data);
+/// loader.prefetch_all();
+/// while let Some(event) = loader.next_event().await {
+///     if let PageEvent::Loaded { index, .. } = event {
+///         let page = loader.page(index).unwrap();
+///         println!("page {} is {}x{}", index + 1, page.width(), page.height());
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ChapterLoader {
+    shared: Arc<Shared>,
+    image_data: ImageDataResponse,
+    semaphore: Arc<Semaphore>,
+    cache: HashMap<usize, DynamicImage>,
+    cache_limit: usize,
+    /// Pages ever requested, so `prefetch_all` never fetches a page twice.
+    requested: HashSet<usize>,
+    in_flight: HashSet<usize>,
+    failed: HashSet<usize>,
+    position: usize,
+    tasks: JoinSet<()>,
+    tx: mpsc::Sender<TaskMsg>,
+    rx: mpsc::Receiver<TaskMsg>,
+}
+
+impl ChapterLoader {
+    /// Creates a loader for chapter `chapter_id`, whose server assignment is
+    /// `image_data`.
+    ///
+    /// Nothing is downloaded until pages are requested.
+    pub fn new(client: &MangaDexClient, chapter_id: &str, image_data: ImageDataResponse) -> Self {
+        let (tx, rx) = mpsc::channel(64);
+        let shared = Shared {
+            images: client.image_client(),
+            chapter_id: chapter_id.to_string(),
+            files: image_data.chapter.clone(),
+            node: Mutex::new(Node {
+                base_url: image_data.base_url.clone(),
+                kind: ServerKind::Assigned,
+                epoch: 0,
+            }),
+            quality: Mutex::new(Quality::new()),
+            failover: tokio::sync::Mutex::new(()),
+        };
+        ChapterLoader {
+            shared: Arc::new(shared),
+            image_data,
+            semaphore: Arc::new(Semaphore::new(MAX_CONCURRENT_FETCHES)),
+            cache: HashMap::new(),
+            cache_limit: MAX_CACHE_PAGES,
+            requested: HashSet::new(),
+            in_flight: HashSet::new(),
+            failed: HashSet::new(),
+            position: 0,
+            tasks: JoinSet::new(),
+            tx,
+            rx,
+        }
+    }
+
+    /// Sets the maximum number of concurrent downloads.
+    pub fn with_concurrency(mut self, limit: usize) -> Self {
+        self.semaphore = Arc::new(Semaphore::new(limit.max(1)));
+        self
+    }
+
+    /// Sets the maximum number of decoded pages kept in memory.
+    pub fn with_cache_limit(mut self, limit: usize) -> Self {
+        self.cache_limit = limit.max(1);
+        self
+    }
+
+    /// Sets how page quality is chosen.
+    pub fn with_quality_mode(self, mode: QualityMode) -> Self {
+        self.set_quality_mode(mode);
+        self
+    }
+
+    /// Changes how page quality is chosen. Applies to every download that
+    /// has not started yet.
+    pub fn set_quality_mode(&self, mode: QualityMode) {
+        self.shared.quality().mode = mode;
+    }
+
+    /// How page quality is chosen.
+    pub fn quality_mode(&self) -> QualityMode {
+        self.shared.quality().mode
+    }
+
+    /// The quality new downloads use, after resolving
+    /// [`QualityMode::Auto`] and chapters without data-saver pages.
+    pub fn quality(&self) -> ImageQuality {
+        self.shared.current_quality()
+    }
+
+    /// Download speed of recent pages in bytes per second, across all
+    /// concurrent downloads, once a page has been downloaded.
+    pub fn throughput(&self) -> Option<f64> {
+        self.shared.quality().throughput
+    }
+
+    /// Server and file information for the chapter, as first assigned.
+    pub fn image_data(&self) -> &ImageDataResponse {
+        &self.image_data
+    }
+
+    /// Base URL of the server pages are currently downloaded from.
+    pub fn server_url(&self) -> String {
+        self.shared.node().base_url.clone()
+    }
+
+    /// Which kind of server pages are currently downloaded from.
+    pub fn server_kind(&self) -> ServerKind {
+        self.shared.node().kind
+    }
+
+    /// Number of pages in the chapter.
+    pub fn page_count(&self) -> usize {
+        self.image_data.chapter.data.len()
+    }
+
+    /// Number of decoded pages currently cached.
+    pub fn cached_count(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// URL of the page at `index` on the current server and in the current
+    /// quality, or `None` if out of range.
+    pub fn page_url(&self, index: usize) -> Option<String> {
+        Some(self.shared.page_url(index)?.0)
+    }
+
+    /// The decoded page at `index`, if it is cached.
+    pub fn page(&self, index: usize) -> Option<&DynamicImage> {
+        self.cache.get(&index)
+    }
+
+    /// Returns `true` if the last download of the page at `index` failed.
+    pub fn is_failed(&self, index: usize) -> bool {
+        self.failed.contains(&index)
+    }
+
+    /// Number of pages whose last download failed.
+    pub fn failed_count(&self) -> usize {
+        self.failed.len()
+    }
+
+    /// Sets the page being read; eviction drops the pages farthest from it.
+    pub fn set_position(&mut self, index: usize) {
+        self.position = index;
+    }
+
+    /// Starts downloading the page at `index` unless it is cached, already
+    /// downloading or out of range. A failed page is retried.
+    pub fn request(&mut self, index: usize) {
+        if index >= self.page_count()
+            || self.cache.contains_key(&index)
+            || !self.in_flight.insert(index)
+        {
+            return;
+        }
+        self.requested.insert(index);
+        self.failed.remove(&index);
+
+        let shared = self.shared.clone();
+        let semaphore = self.semaphore.clone();
+        let tx = self.tx.clone();
+        self.tasks.spawn(async move {
+            // Hold the permit for the whole download to cap concurrency.
+            let result = match semaphore.acquire_owned().await {
+                Ok(_permit) => shared.load_page(index, &tx).await,
+                Err(e) => Err(YomuError::Io(std::io::Error::other(e))),
+            };
+            let _ = tx.send(TaskMsg::Page(index, result)).await;
+        });
+    }
+
+    /// Starts downloading every page that has never been requested.
+    ///
+    /// Pages evicted from the cache are not fetched again; use
+    /// [`request`](Self::request) to bring one back.
+    pub fn prefetch_all(&mut self) {
+        for index in 0..self.page_count() {
+            if !self.requested.contains(&index) {
+                self.request(index);
+            }
+        }
+    }
+
+    /// Downloads every failed page again and returns how many were retried.
+    ///
+    /// Downloads already retry transient errors on their own; this is for
+    /// pages that failed even so, e.g. after the network came back.
+    pub fn retry_failed(&mut self) -> usize {
+        let failed: Vec<usize> = self.failed.iter().copied().collect();
+        for &index in &failed {
+            self.request(index);
+        }
+        failed.len()
+    }
+
+    /// Waits for the next page to finish, or returns `None` if no download is in flight.
+    pub async fn next_event(&mut self) -> Option<PageEvent> {
+        if self.in_flight.is_empty() {
+            return None;
+        }
+        let msg = self.rx.recv().await?;
+        Some(self.handle(msg))
+    }
+
+    /// Returns the next finished page without waiting, if there is one.
+    pub fn try_next_event(&mut self) -> Option<PageEvent> {
+        let msg = self.rx.try_recv().ok()?;
+        Some(self.handle(msg))
+    }
+
+    fn handle(&mut self, msg: TaskMsg) -> PageEvent {
+        match msg {
+            TaskMsg::Page(index, result) => self.apply(index, result),
+            TaskMsg::Server(base_url, kind) => PageEvent::ServerChanged { base_url, kind },
+        }
+    }
+
+    /// Records a finished download in the cache and turns it into an event.
+    fn apply(&mut self, index: usize, result: Result<DynamicImage>) -> PageEvent {
+        self.in_flight.remove(&index);
+        // Reap finished tasks so the set does not grow for the whole chapter.
+        while self.tasks.try_join_next().is_some() {}
+        match result {
+            Ok(image) => {
+                self.cache.insert(index, image);
+                PageEvent::Loaded {
+                    index,
+                    evicted: self.evict(),
+                }
+            }
+            Err(error) => {
+                self.failed.insert(index);
+                PageEvent::Failed { index, error }
+            }
+        }
+    }
+
+    /// Drops the page farthest from the reading position if the cache is over its limit.
+    fn evict(&mut self) -> Option<usize> {
+        if self.cache.len() <= self.cache_limit {
+            return None;
+        }
+        let position = self.position;
+        let farthest = *self.cache.keys().max_by_key(|&&k| k.abs_diff(position))?;
+        self.cache.remove(&farthest);
+        Some(farthest)
+    }
+}
+
+impl Shared {
+    fn node(&self) -> MutexGuard<'_, Node> {
+        self.node.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn quality(&self) -> MutexGuard<'_, Quality> {
+        self.quality.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn current_quality(&self) -> ImageQuality {
+        let quality = self.quality().current();
+        self.files.available(quality)
+    }
+
+    /// URL of page `index` on the current server in the current quality,
+    /// with the server's epoch.
+    fn page_url(&self, index: usize) -> Option<(String, u64)> {
+        let (quality, file) = self.files.page_file(index, self.current_quality())?;
+        let node = self.node();
+        let url = format!(
+            "{}/{}/{}/{}",
+            node.base_url,
+            quality.path(),
+            self.files.hash,
+            file
+        );
+        Some((url, node.epoch))
+    }
+
+    /// Downloads a page, moving to another server if the current one fails,
+    /// and decodes it on a blocking thread.
+    async fn load_page(&self, index: usize, tx: &mpsc::Sender<TaskMsg>) -> Result<DynamicImage> {
+        let bytes = loop {
+            let Some((url, epoch)) = self.page_url(index) else {
+                return Err(YomuError::Io(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("page {index} is out of range"),
+                )));
+            };
+            match self.images.download_timed(&url).await {
+                Ok((bytes, started)) => {
+                    self.quality().record(bytes.len(), started, Instant::now());
+                    break bytes;
+                }
+                Err(error) => self.recover(error, epoch, tx).await?,
+            }
+        };
+        let limits = *self.images.client.decode_limits();
+        tokio::task::spawn_blocking(move || limits.decode(&bytes))
+            .await
+            .map_err(|e| YomuError::Io(std::io::Error::other(e)))?
+    }
+
+    /// Handles a failed download from the server that was current at
+    /// `epoch`: moves to another server if that might help, and otherwise
+    /// returns the error. Errors such as an oversized or missing page would
+    /// fail the same way anywhere, so they leave the server alone.
+    async fn recover(
+        &self,
+        error: YomuError,
+        epoch: u64,
+        tx: &mpsc::Sender<TaskMsg>,
+    ) -> Result<()> {
+        let another_server_may_help = match &error {
+            // Not worth retrying on the same node, but another one may well
+            // have an intact copy.
+            YomuError::Integrity { .. } => true,
+            error => error.is_retryable(),
+        };
+        if another_server_may_help && self.fail_over(epoch, tx).await {
+            Ok(())
+        } else {
+            Err(error)
+        }
+    }
+
+    /// Moves off the server that was current at `epoch`. Returns `false` if
+    /// there is nowhere left to go.
+    async fn fail_over(&self, epoch: u64, tx: &mpsc::Sender<TaskMsg>) -> bool {
+        let _guard = self.failover.lock().await;
+        let (failed_url, kind) = {
+            let node = self.node();
+            if node.epoch != epoch {
+                // Another task already switched servers; just try again.
+                return true;
+            }
+            (node.base_url.clone(), node.kind)
+        };
+        let (base_url, kind) = match kind {
+            ServerKind::Assigned => match self.images.refresh_image_data(&self.chapter_id).await {
+                Ok(data) if data.base_url != failed_url => (data.base_url, ServerKind::Reassigned),
+                _ => (UPLOADS_BASE_URL.to_string(), ServerKind::Upstream),
+            },
+            ServerKind::Reassigned => (UPLOADS_BASE_URL.to_string(), ServerKind::Upstream),
+            ServerKind::Upstream => return false,
+        };
+        {
+            let mut node = self.node();
+            node.base_url = base_url.clone();
+            node.kind = kind;
+            node.epoch += 1;
+        }
+        let _ = tx.send(TaskMsg::Server(base_url, kind)).await;
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::SizeLimit;
+
+    #[test]
+    fn cache_evicts_the_page_farthest_from_the_reader() {
+        let client = MangaDexClient::new().unwrap();
+        let image_data = ImageDataResponse {
+            result: "ok".to_string(),
+            base_url: "https://example.org".to_string(),
+            chapter: ImageAttributes {
+                hash: "hash".to_string(),
+                data: (0..10).map(|i| format!("{i}.png")).collect(),
+                data_saver: Vec::new(),
+            },
+        };
+        let mut loader = ChapterLoader::new(&client, "chapter", image_data).with_cache_limit(2);
+        assert_eq!(
+            loader.page_url(3).as_deref(),
+            Some("https://example.org/data/hash/3.png")
+        );
+
+        let page = || Ok(DynamicImage::new_rgb8(1, 1));
+        loader.set_position(4);
+        assert!(matches!(
+            loader.apply(4, page()),
+            PageEvent::Loaded { evicted: None, .. }
+        ));
+        assert!(matches!(
+            loader.apply(9, page()),
+            PageEvent::Loaded { evicted: None, .. }
+        ));
+        assert!(matches!(
+            loader.apply(5, page()),
+            PageEvent::Loaded {
+                evicted: Some(9),
+                ..
+            }
+        ));
+        assert!(loader.page(4).is_some() && loader.page(5).is_some());
+    }
+
+    #[test]
+    fn pages_missing_from_data_saver_fall_back_to_originals() {
+        let client = MangaDexClient::new().unwrap();
+        let image_data = ImageDataResponse {
+            result: "ok".to_string(),
+            base_url: "https://example.org".to_string(),
+            chapter: ImageAttributes {
+                hash: "hash".to_string(),
+                data: vec!["1.png".to_string(), "2.png".to_string()],
+                data_saver: vec!["1.jpg".to_string()],
+            },
+        };
+        let loader = ChapterLoader::new(&client, "chapter", image_data);
+        loader.set_quality_mode(QualityMode::DataSaver);
+        assert_eq!(loader.page_count(), 2);
+        assert_eq!(
+            loader.page_url(0).as_deref(),
+            Some("https://example.org/data-saver/hash/1.jpg")
+        );
+        assert_eq!(
+            loader.page_url(1).as_deref(),
+            Some("https://example.org/data/hash/2.png")
+        );
+        assert_eq!(loader.page_url(2), None);
+    }
+
+    #[test]
+    fn auto_quality_follows_throughput_with_hysteresis() {
+        let mut quality = Quality::new();
+        quality.mode = QualityMode::Auto;
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+        quality.record(100 * 1024, at(0), at(1));
+        assert_eq!(quality.current(), ImageQuality::DataSaver);
+        // Between the thresholds nothing changes.
+        quality.record(500 * 1024, at(1), at(2));
+        assert!(quality.throughput.unwrap() < AUTO_FAST_BYTES_PER_SEC);
+        assert_eq!(quality.current(), ImageQuality::DataSaver);
+        for secs in 2..6 {
+            quality.record(1024 * 1024, at(secs), at(secs + 1));
+        }
+        assert_eq!(quality.current(), ImageQuality::Original);
+        // Downloads outside the window are forgotten.
+        quality.record(100 * 1024, at(30), at(31));
+        assert_eq!(quality.current(), ImageQuality::DataSaver);
+
+        quality.mode = QualityMode::DataSaver;
+        assert_eq!(quality.current(), ImageQuality::DataSaver);
+    }
+
+    #[test]
+    fn concurrent_downloads_share_the_measured_throughput() {
+        let mut quality = Quality::new();
+        quality.mode = QualityMode::Auto;
+        // Eight pages of 128 KiB downloaded side by side over one second:
+        // each alone looks like 128 KiB/s, together they are 1 MiB/s.
+        let start = Instant::now();
+        for i in 0..8 {
+            let started = start + Duration::from_millis(i * 10);
+            let finished = start + Duration::from_millis(930 + i * 10);
+            quality.record(128 * 1024, started, finished);
+        }
+        let throughput = quality.throughput.unwrap();
+        assert!((throughput - 1024.0 * 1024.0).abs() < 1.0, "{throughput}");
+        assert_eq!(quality.current(), ImageQuality::Original);
+
+        // An idle gap between downloads does not count as busy time.
+        let mut quality = Quality::new();
+        quality.record(512 * 1024, start, start + Duration::from_millis(500));
+        let later = start + Duration::from_secs(5);
+        quality.record(512 * 1024, later, later + Duration::from_millis(500));
+        let throughput = quality.throughput.unwrap();
+        assert!((throughput - 1024.0 * 1024.0).abs() < 1.0, "{throughput}");
+    }
+
+    /// Loader state for a one-page chapter on a reassigned @Home node, so
+    /// failing over needs no API call.
+    fn reassigned_shared(epoch: u64) -> Shared {
+        let client = MangaDexClient::new().unwrap();
+        Shared {
+            images: client.image_client(),
+            chapter_id: "chapter".to_string(),
+            files: ImageAttributes {
+                hash: "hash".to_string(),
+                data: vec!["1.png".to_string()],
+                data_saver: vec!["1.jpg".to_string()],
+            },
+            node: Mutex::new(Node {
+                base_url: "https://node.mangadex.network".to_string(),
+                kind: ServerKind::Reassigned,
+                epoch,
+            }),
+            quality: Mutex::new(Quality::new()),
+            failover: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    #[tokio::test]
+    async fn failover_ends_at_the_upstream_server() {
+        let shared = reassigned_shared(3);
+        let (tx, mut rx) = mpsc::channel(4);
+
+        assert!(shared.fail_over(3, &tx).await);
+        assert_eq!(
+            shared.page_url(0),
+            Some((format!("{UPLOADS_BASE_URL}/data/hash/1.png"), 4))
+        );
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(TaskMsg::Server(_, ServerKind::Upstream))
+        ));
+        // A failure seen before the switch just retries on the new server.
+        assert!(shared.fail_over(3, &tx).await);
+        assert!(!shared.fail_over(4, &tx).await);
+    }
+
+    #[tokio::test]
+    async fn only_transient_errors_move_to_another_server() {
+        let shared = reassigned_shared(0);
+        let (tx, mut rx) = mpsc::channel(4);
+
+        let too_large = YomuError::SizeLimit(SizeLimit::Dimensions {
+            width: 1,
+            height: 100_000,
+        });
+        assert!(matches!(
+            shared.recover(too_large, 0, &tx).await,
+            Err(YomuError::SizeLimit(_))
+        ));
+        let missing = YomuError::NotFound {
+            url: "https://node.mangadex.network/data/hash/1.png".to_string(),
+        };
+        assert!(shared.recover(missing, 0, &tx).await.is_err());
+        assert_eq!(shared.node().kind, ServerKind::Reassigned);
+        assert!(rx.try_recv().is_err());
+
+        let timeout = YomuError::Timeout { url: None };
+        assert!(shared.recover(timeout, 0, &tx).await.is_ok());
+        assert_eq!(shared.node().kind, ServerKind::Upstream);
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(TaskMsg::Server(_, ServerKind::Upstream))
+        ));
+    }
+
+    #[tokio::test]
+    async fn hash_mismatch_moves_to_another_server() {
+        let shared = reassigned_shared(0);
+        let (tx, mut rx) = mpsc::channel(4);
+        let url = format!(
+            "https://node.mangadex.network/data/hash/1-{}.png",
+            "0".repeat(64)
+        );
+        let mismatch = crate::image::verify_page_hash(&url, b"truncated").unwrap_err();
+        assert!(matches!(mismatch, YomuError::Integrity { .. }));
+        assert!(!mismatch.is_retryable());
+
+        assert!(shared.recover(mismatch, 0, &tx).await.is_ok());
+        assert_eq!(shared.node().kind, ServerKind::Upstream);
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(TaskMsg::Server(_, ServerKind::Upstream))
+        ));
+    }
+}
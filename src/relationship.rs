@@ -0,0 +1,255 @@
+use crate::search::MangaAttributes;
+use serde::Deserialize;
+
+/// A link from one MangaDex entity to another, such as a manga's author or a
+/// chapter's scanlation group.
+///
+/// `attributes` is only present when the related entity was expanded with
+/// `includes[]` in the request (see [`Include`]).
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(from = "RawRelationship")]
+pub struct Relationship {
+    /// Unique identifier of the related entity.
+    pub id: String,
+    /// Kind of the related entity.
+    pub kind: RelationshipKind,
+    /// How two manga are related (e.g. "sequel"), for manga-to-manga links.
+    pub related: Option<String>,
+    /// Attributes of the related entity, when expanded.
+    pub attributes: Option<RelationshipAttributes>,
+}
+
+impl Relationship {
+    /// Returns the author attributes if this is an expanded author or artist.
+    pub fn as_author(&self) -> Option<&AuthorAttributes> {
+        match &self.attributes {
+            Some(RelationshipAttributes::Author(author)) => Some(author),
+            _ => None,
+        }
+    }
+
+    /// Returns the cover attributes if this is an expanded cover art.
+    pub fn as_cover_art(&self) -> Option<&CoverArtAttributes> {
+        match &self.attributes {
+            Some(RelationshipAttributes::CoverArt(cover)) => Some(cover),
+            _ => None,
+        }
+    }
+
+    /// Returns the group attributes if this is an expanded scanlation group.
+    pub fn as_scanlation_group(&self) -> Option<&ScanlationGroupAttributes> {
+        match &self.attributes {
+            Some(RelationshipAttributes::ScanlationGroup(group)) => Some(group),
+            _ => None,
+        }
+    }
+
+    /// Returns the manga attributes if this is an expanded manga.
+    pub fn as_manga(&self) -> Option<&MangaAttributes> {
+        match &self.attributes {
+            Some(RelationshipAttributes::Manga(manga)) => Some(manga),
+            _ => None,
+        }
+    }
+
+    /// Returns the user attributes if this is an expanded user.
+    pub fn as_user(&self) -> Option<&UserAttributes> {
+        match &self.attributes {
+            Some(RelationshipAttributes::User(user)) => Some(user),
+            _ => None,
+        }
+    }
+}
+
+/// The kind of entity a [`Relationship`] points at.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RelationshipKind {
+    Manga,
+    Chapter,
+    CoverArt,
+    Author,
+    Artist,
+    ScanlationGroup,
+    Tag,
+    User,
+    CustomList,
+    Creator,
+    Leader,
+    Member,
+    /// Any kind this crate does not model yet.
+    #[serde(other)]
+    Other,
+}
+
+/// Typed attributes of an expanded [`Relationship`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum RelationshipAttributes {
+    /// An author or artist (MangaDex models both as authors).
+    Author(AuthorAttributes),
+    /// A cover image.
+    CoverArt(CoverArtAttributes),
+    /// A scanlation group.
+    ScanlationGroup(ScanlationGroupAttributes),
+    /// A manga, e.g. the parent of a chapter.
+    Manga(Box<MangaAttributes>),
+    /// A user, e.g. the uploader of a chapter.
+    User(UserAttributes),
+    /// Attributes of a kind this crate does not model, or that failed to parse.
+    Other(serde_json::Value),
+}
+
+/// Attributes of an author or artist.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct AuthorAttributes {
+    /// The author's name.
+    pub name: String,
+}
+
+/// Attributes of a cover image.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct CoverArtAttributes {
+    /// File name of the cover under `uploads.mangadex.org/covers/{manga_id}/`.
+    #[serde(rename = "fileName")]
+    pub file_name: String,
+    /// Volume the cover belongs to.
+    pub volume: Option<String>,
+    /// Language of the cover.
+    pub locale: Option<String>,
+}
+
+/// Attributes of a scanlation group.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ScanlationGroupAttributes {
+    /// The group's name.
+    pub name: String,
+    /// The group's website, if any.
+    pub website: Option<String>,
+    /// Whether the group is an official publisher.
+    #[serde(default)]
+    pub official: bool,
+}
+
+/// Attributes of a user.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct UserAttributes {
+    /// The user's name.
+    pub username: String,
+}
+
+/// A related entity that can be expanded inline with `includes[]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Include {
+    CoverArt,
+    Author,
+    Artist,
+    ScanlationGroup,
+    Manga,
+    User,
+}
+
+impl Include {
+    /// The value MangaDex uses for this expansion.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Include::CoverArt => "cover_art",
+            Include::Author => "author",
+            Include::Artist => "artist",
+            Include::ScanlationGroup => "scanlation_group",
+            Include::Manga => "manga",
+            Include::User => "user",
+        }
+    }
+}
+
+/// Converts a list of expansions into `includes[]` query pairs, skipping
+/// repeats so each expansion is requested once.
+pub(crate) fn include_pairs(includes: &[Include]) -> Vec<(&'static str, &'static str)> {
+    let mut pairs = Vec::with_capacity(includes.len());
+    for include in includes {
+        let pair = ("includes[]", include.as_str());
+        if !pairs.contains(&pair) {
+            pairs.push(pair);
+        }
+    }
+    pairs
+}
+
+/// The relationship exactly as MangaDex sends it, before typing the attributes.
+#[derive(Deserialize)]
+struct RawRelationship {
+    id: String,
+    #[serde(rename = "type")]
+    kind: RelationshipKind,
+    related: Option<String>,
+    attributes: Option<serde_json::Value>,
+}
+
+impl From<RawRelationship> for Relationship {
+    fn from(raw: RawRelationship) -> Self {
+        let attributes = raw.attributes.map(|value| {
+            let typed = match raw.kind {
+                RelationshipKind::Author | RelationshipKind::Artist => {
+                    serde_json::from_value(value.clone()).map(RelationshipAttributes::Author)
+                }
+                RelationshipKind::CoverArt => {
+                    serde_json::from_value(value.clone()).map(RelationshipAttributes::CoverArt)
+                }
+                RelationshipKind::ScanlationGroup => serde_json::from_value(value.clone())
+                    .map(RelationshipAttributes::ScanlationGroup),
+                RelationshipKind::Manga => serde_json::from_value(value.clone())
+                    .map(|m| RelationshipAttributes::Manga(Box::new(m))),
+                RelationshipKind::User
+                | RelationshipKind::Creator
+                | RelationshipKind::Leader
+                | RelationshipKind::Member => {
+                    serde_json::from_value(value.clone()).map(RelationshipAttributes::User)
+                }
+                _ => Ok(RelationshipAttributes::Other(value.clone())),
+            };
+            typed.unwrap_or(RelationshipAttributes::Other(value))
+        });
+        Relationship {
+            id: raw.id,
+            kind: raw.kind,
+            related: raw.related,
+            attributes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn include_pairs_skip_repeats() {
+        let pairs = include_pairs(&[Include::CoverArt, Include::Author, Include::CoverArt]);
+        assert_eq!(
+            pairs,
+            [("includes[]", "cover_art"), ("includes[]", "author")]
+        );
+    }
+
+    #[test]
+    fn expanded_relationships_are_typed() {
+        let json = r#"[
+            {"id": "a", "type": "author", "attributes": {"name": "Tatsuki Fujimoto"}},
+            {"id": "c", "type": "cover_art", "attributes": {"fileName": "cover.jpg", "volume": "1", "locale": "ja"}},
+            {"id": "g", "type": "scanlation_group", "attributes": {"name": "Group", "website": null}},
+            {"id": "u", "type": "user"},
+            {"id": "x", "type": "something_new", "attributes": {"foo": 1}}
+        ]"#;
+        let rels: Vec<Relationship> = serde_json::from_str(json).unwrap();
+        assert_eq!(rels[0].as_author().unwrap().name, "Tatsuki Fujimoto");
+        assert_eq!(rels[1].as_cover_art().unwrap().file_name, "cover.jpg");
+        assert_eq!(rels[2].as_scanlation_group().unwrap().name, "Group");
+        assert_eq!(rels[3].kind, RelationshipKind::User);
+        assert!(rels[3].attributes.is_none());
+        assert_eq!(rels[4].kind, RelationshipKind::Other);
+        assert!(matches!(
+            rels[4].attributes,
+            Some(RelationshipAttributes::Other(_))
+        ));
+    }
+}
@@ -0,0 +1,155 @@
+use serde::Serialize;
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Where MangaDex@Home download reports are sent by default.
+pub const DEFAULT_REPORT_URL: &str = "https://api.mangadex.network/report";
+
+/// Reports are sent once this many are queued...
+const REPORT_BATCH: usize = 16;
+/// ...or after this long, whichever comes first.
+const REPORT_INTERVAL: Duration = Duration::from_secs(10);
+/// Reports queued beyond this are dropped rather than slowing downloads.
+const REPORT_QUEUE: usize = 256;
+
+/// The outcome of one image download from an @Home node, in the format the
+/// MangaDex@Home network expects.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    /// The full image URL.
+    pub url: String,
+    /// Whether the image was downloaded successfully.
+    pub success: bool,
+    /// Whether the node served the image from its cache (`X-Cache: HIT`).
+    pub cached: bool,
+    /// Size of the response body in bytes.
+    pub bytes: usize,
+    /// Time taken by the download, in milliseconds.
+    pub duration: u64,
+}
+
+/// Sends [`Report`]s in the background, in batches.
+///
+/// Reporting never blocks or fails a download: reports are queued and sent
+/// by a tokio task, and dropped if the queue is full or sending fails.
+pub(crate) struct Reporter {
+    http: reqwest::Client,
+    url: String,
+    queue: OnceLock<mpsc::Sender<Report>>,
+}
+
+impl Reporter {
+    pub(crate) fn new(http: reqwest::Client, url: String) -> Self {
+        Reporter {
+            http,
+            url,
+            queue: OnceLock::new(),
+        }
+    }
+
+    /// Queues a report, starting the sending task on first use.
+    pub(crate) fn report(&self, report: Report) {
+        if is_upstream(&report.url) {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let queue = self.queue.get_or_init(|| {
+            let (tx, rx) = mpsc::channel(REPORT_QUEUE);
+            runtime.spawn(send_reports(self.http.clone(), self.url.clone(), rx));
+            tx
+        });
+        let _ = queue.try_send(report);
+    }
+}
+
+/// Returns `true` for downloads from MangaDex's own server, which is not
+/// part of the @Home network.
+fn is_upstream(url: &str) -> bool {
+    reqwest::Url::parse(url).is_ok_and(|url| url.host_str() == Some("uploads.mangadex.org"))
+}
+
+/// Collects reports from `rx` and posts them in batches until the reporter
+/// is dropped, then sends whatever is left.
+async fn send_reports(http: reqwest::Client, url: String, mut rx: mpsc::Receiver<Report>) {
+    let mut batch = Vec::new();
+    let mut ticker = tokio::time::interval(REPORT_INTERVAL);
+    loop {
+        tokio::select! {
+            report = rx.recv() => match report {
+                Some(report) => {
+                    batch.push(report);
+                    if batch.len() < REPORT_BATCH {
+                        continue;
+                    }
+                }
+                None => {
+                    post_batch(&http, &url, &mut batch).await;
+                    return;
+                }
+            },
+            _ = ticker.tick() => {}
+        }
+        post_batch(&http, &url, &mut batch).await;
+    }
+}
+
+/// Posts every report in `batch` (the endpoint takes one per request) and
+/// empties it.
+async fn post_batch(http: &reqwest::Client, url: &str, batch: &mut Vec<Report>) {
+    for report in batch.drain(..) {
+        let _ = http.post(url).json(&report).send().await;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::serve_once;
+
+    fn report(url: &str) -> Report {
+        Report {
+            url: url.to_string(),
+            success: true,
+            cached: false,
+            bytes: 1024,
+            duration: 150,
+        }
+    }
+
+    #[test]
+    fn only_uploads_server_downloads_are_upstream() {
+        assert!(is_upstream("https://uploads.mangadex.org/data/hash/1.png"));
+        assert!(is_upstream(
+            "https://Uploads.MangaDex.org:443/data/hash/1.png"
+        ));
+        assert!(!is_upstream(
+            "https://uploads.mangadex.org.evil.example/data/hash/1.png"
+        ));
+        assert!(!is_upstream(
+            "https://node.mangadex.network/data/hash/1.png"
+        ));
+        assert!(!is_upstream("not a url"));
+    }
+
+    #[tokio::test]
+    async fn reports_reach_a_local_endpoint() {
+        let (base_url, server) = serve_once("HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n").await;
+        let reporter = Reporter::new(reqwest::Client::new(), format!("{base_url}/report"));
+
+        reporter.report(report("https://uploads.mangadex.org/data/hash/skipped.png"));
+        reporter.report(report("https://node.mangadex.network/data/hash/1.png"));
+        // Dropping the reporter flushes its queue.
+        drop(reporter);
+
+        let request = tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(request.starts_with("POST /report "));
+        assert!(request.contains("\"url\":\"https://node.mangadex.network/data/hash/1.png\""));
+        assert!(!request.contains("skipped.png"));
+    }
+}
@@ -0,0 +1,183 @@
+use crate::error::{Result, YomuError};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// How often and how patiently failed requests are retried.
+///
+/// Only transient failures are retried (see [`YomuError::is_retryable`]):
+/// timeouts, dropped connections, `408`, `429` and `5xx` responses. Between
+/// attempts the client sleeps for a random delay of up to
+/// `base_delay * 2^attempt`, capped at `max_delay` ("full jitter"), so many
+/// clients failing together do not retry in lockstep. A rate limited
+/// request waits at least as long as the server's `Retry-After`, and is not
+/// retried if that is longer than `max_delay`.
+///
+/// Set with [`MangaDexClientBuilder::retry_policy`](crate::client::MangaDexClientBuilder::retry_policy).
+///
+/// [`YomuError::is_retryable`]: crate::error::YomuError::is_retryable
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt; `0` disables retrying.
+    pub max_retries: u32,
+    /// Upper bound of the first delay.
+    pub base_delay: Duration,
+    /// Upper bound of any delay.
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub const fn none() -> Self {
+        RetryPolicy {
+            max_retries: 0,
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+        }
+    }
+
+    /// Upper bound of the delay before retry number `attempt` (starting at 0).
+    pub fn max_backoff(&self, attempt: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay)
+    }
+
+    /// A random delay between zero and [`max_backoff`](Self::max_backoff).
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        self.max_backoff(attempt).mul_f64(random_fraction())
+    }
+
+    /// How long to wait before retrying after `error`, or `None` if the
+    /// server asked for a longer wait than this policy allows.
+    fn retry_delay(&self, error: &YomuError, attempt: u32) -> Option<Duration> {
+        let backoff = self.backoff(attempt);
+        match error {
+            YomuError::RateLimited {
+                retry_after: Some(wait),
+            } => (*wait <= self.max_delay).then(|| backoff.max(*wait)),
+            _ => Some(backoff),
+        }
+    }
+
+    /// Runs `op` until it succeeds, fails with an error that is not
+    /// retryable, or runs out of retries.
+    pub(crate) async fn run<T, F, Fut>(&self, mut op: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match op().await {
+                Err(e) if attempt < self.max_retries && e.is_retryable() => {
+                    let Some(delay) = self.retry_delay(&e, attempt) else {
+                        return Err(e);
+                    };
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+/// A random number in `[0, 1)`, seeded from the standard library's
+/// per-process hash keys so no RNG dependency is needed.
+fn random_fraction() -> f64 {
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::cell::Cell;
+    use std::io::{Error, ErrorKind};
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.max_backoff(0), Duration::from_millis(500));
+        assert_eq!(policy.max_backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.max_backoff(10), Duration::from_secs(10));
+        for attempt in 0..5 {
+            assert!(policy.backoff(attempt) <= policy.max_backoff(attempt));
+        }
+    }
+
+    #[tokio::test]
+    async fn run_retries_only_transient_errors() {
+        let policy = RetryPolicy {
+            max_retries: 2,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(1),
+        };
+
+        let calls = Cell::new(0);
+        let result: Result<()> = policy
+            .run(|| async {
+                calls.set(calls.get() + 1);
+                Err(YomuError::Io(Error::from(ErrorKind::ConnectionReset)))
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(calls.get(), 3);
+
+        calls.set(0);
+        let result: Result<()> = policy
+            .run(|| async {
+                calls.set(calls.get() + 1);
+                Err(YomuError::Io(Error::from(ErrorKind::InvalidData)))
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[tokio::test]
+    async fn run_honours_retry_after() {
+        let policy = RetryPolicy {
+            max_retries: 1,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_secs(1),
+        };
+        let rate_limited = |secs: f64| YomuError::RateLimited {
+            retry_after: Some(Duration::from_secs_f64(secs)),
+        };
+
+        let calls = Cell::new(0);
+        let started = std::time::Instant::now();
+        let result: Result<()> = policy
+            .run(|| async {
+                calls.set(calls.get() + 1);
+                Err(rate_limited(0.05))
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(calls.get(), 2);
+        assert!(started.elapsed() >= Duration::from_millis(50));
+
+        // A longer wait than the policy allows is not retried at all.
+        calls.set(0);
+        let result: Result<()> = policy
+            .run(|| async {
+                calls.set(calls.get() + 1);
+                Err(rate_limited(30.0))
+            })
+            .await;
+        assert!(matches!(result, Err(YomuError::RateLimited { .. })));
+        assert_eq!(calls.get(), 1);
+    }
+}
@@ -1,20 +1,38 @@
 use crate::client::MangaDexClient;
 use crate::error::Result;
+use crate::query::SearchQuery;
+use crate::ratelimit::Endpoint;
+use crate::relationship::Relationship;
+use crate::relationship::{Include, include_pairs};
+use crate::tag::Tag;
 use serde::Deserialize;
 use std::collections::HashMap;
 
 /// A client for searching manga.
-pub struct SearchClient<'mangaclient> {
-    /// Reference to the parent `MangaDexClient`.
-    pub client: &'mangaclient MangaDexClient,
+#[derive(Clone)]
+pub struct SearchClient {
+    /// The parent `MangaDexClient` (a cheap clone sharing its connection pool).
+    pub client: MangaDexClient,
+    /// Related entities to expand inline in every response.
+    pub includes: Vec<Include>,
 }
 
-impl<'mangaclient> SearchClient<'mangaclient> {
-    /// Searches for manga by title and filters out results with "Unknown Title".
+impl SearchClient {
+    /// Requests that the given related entities be expanded inline, so their
+    /// attributes are available on each result's `relationships`.
+    pub fn with_includes(mut self, includes: &[Include]) -> Self {
+        self.includes.extend_from_slice(includes);
+        self
+    }
+
+    /// Searches for manga by title.
+    ///
+    /// Only the first page of results is returned; use
+    /// [`search_page`](Self::search_page) to page through the rest.
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,no_run
     /// # use yomu::MangaDexClient;
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -24,28 +42,101 @@ impl<'mangaclient> SearchClient<'mangaclient> {
     /// # }
     /// ```
     pub async fn search(&self, title: String) -> Result<Vec<MangaData>> {
-        let resp: reqwest::Response = self
+        Ok(self.search_page(&title, 0, SEARCH_PAGE_LIMIT).await?.data)
+    }
+
+    /// Fetches one page of search results starting at `offset`.
+    ///
+    /// The returned [`SearchPage`] reports the total number of matches so
+    /// callers can resume with [`SearchPage::next_offset`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use yomu::MangaDexClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = MangaDexClient::new()?;
+    /// let search_client = client.search_client();
+    /// let first = search_client.search_page("one piece", 0, 20).await?;
+    /// if let Some(offset) = first.next_offset() {
+    ///     let second = search_client.search_page("one piece", offset, 20).await?;
+    ///     println!("{} of {} results", first.data.len() + second.data.len(), first.total);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn search_page(
+        &self,
+        title: &str,
+        offset: usize,
+        limit: usize,
+    ) -> Result<SearchPage> {
+        self.search_query(&SearchQuery::new().title(title), offset, limit)
+            .await
+    }
+
+    /// Fetches one page of results for a structured [`SearchQuery`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use yomu::MangaDexClient;
+    /// use yomu::query::{Demographic, MangaStatus, SearchQuery};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = MangaDexClient::new()?;
+    /// let query = SearchQuery::new()
+    ///     .status(MangaStatus::Completed)
+    ///     .publication_demographic(Demographic::Seinen);
+    /// let page = client.search_client().search_query(&query, 0, 20).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn search_query(
+        &self,
+        query: &SearchQuery,
+        offset: usize,
+        limit: usize,
+    ) -> Result<SearchPage> {
+        let request = self
             .client
             .http_client()
-            .get(&format!("{}/manga", self.client.base_url))
-            .query(&[("title", title)])
-            .send()
-            .await?
-            .error_for_status()?;
+            .get(format!("{}/manga", self.client.base_url()))
+            .query(&query.query_pairs())
+            .query(&include_pairs(&self.includes))
+            .query(&[("limit", limit), ("offset", offset)]);
+        let resp = self.client.send(Endpoint::Api, request).await?;
         let resp_json = resp.json::<SearchResponse>().await?;
-        let filtered = resp_json
-            .data
-            .into_iter()
-            .filter(|manga| {
-                manga
-                    .attributes
-                    .title
-                    .as_ref()
-                    .and_then(|titles| titles.get("en"))
-                    .is_some_and(|t| t != "Unknown Title")
-            })
-            .collect();
-        Ok(filtered)
+        Ok(SearchPage {
+            data: resp_json.data,
+            offset: resp_json.offset,
+            limit: resp_json.limit,
+            total: resp_json.total,
+        })
+    }
+}
+
+/// Default number of results requested per search page.
+pub const SEARCH_PAGE_LIMIT: usize = 20;
+
+/// A single page of search results returned by [`SearchClient::search_page`].
+pub struct SearchPage {
+    /// Manga on this page.
+    pub data: Vec<MangaData>,
+    /// Offset this page started at.
+    pub offset: usize,
+    /// Number of items the API was asked for.
+    pub limit: usize,
+    /// Total number of items matching the query.
+    pub total: usize,
+}
+
+impl SearchPage {
+    /// Returns the offset of the following page, or `None` if this was the last one.
+    pub fn next_offset(&self) -> Option<usize> {
+        let next = self.offset + self.limit.max(1);
+        (next < self.total).then_some(next)
     }
 }
 /// Response from the MangaDex API for a manga search request.
@@ -74,18 +165,56 @@ pub struct MangaData {
     pub type_: String,
     /// Attributes containing manga information.
     pub attributes: MangaAttributes,
+    /// Related entities such as authors, artists and cover art.
+    #[serde(default)]
+    pub relationships: Vec<Relationship>,
 }
 /// Attributes associated with a manga.
 #[derive(Deserialize, Debug, Clone, PartialEq)]
 pub struct MangaAttributes {
     /// Map of titles in different languages.
     pub title: Option<HashMap<String, String>>,
+    /// Alternative titles, each a single-entry map from language to title.
+    #[serde(rename = "altTitles", default)]
+    pub alt_titles: Vec<HashMap<String, String>>,
     /// Map of descriptions in different languages.
     pub description: Option<HashMap<String, String>>,
     /// Targeted publication demographic.
+    #[serde(rename = "publicationDemographic")]
     pub publication_demographic: Option<String>,
     /// Serialization status of the manga.
     pub status: Option<String>,
     /// Year of publication.
     pub year: Option<usize>,
+    /// Tags (genres, themes, formats and content warnings) applied to the manga.
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn page(offset: usize, limit: usize, total: usize) -> SearchPage {
+        SearchPage {
+            data: Vec::new(),
+            offset,
+            limit,
+            total,
+        }
+    }
+
+    #[test]
+    fn next_offset_stops_after_the_last_page() {
+        assert_eq!(page(0, 20, 50).next_offset(), Some(20));
+        assert_eq!(page(20, 20, 50).next_offset(), Some(40));
+        // The last page ends exactly at the total...
+        assert_eq!(page(40, 10, 50).next_offset(), None);
+        // ...or asked for more than were left.
+        assert_eq!(page(40, 20, 50).next_offset(), None);
+        assert_eq!(page(0, 20, 0).next_offset(), None);
+        // A zero limit still moves forward instead of asking for the same page.
+        assert_eq!(page(0, 0, 5).next_offset(), Some(1));
+        assert_eq!(page(4, 0, 5).next_offset(), None);
+    }
 }
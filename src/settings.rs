@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use yomu::QualityMode;
+
+/// User preferences that persist between runs, stored as JSON in the
+/// platform config directory (`$XDG_CONFIG_HOME/yomu/settings.json`).
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Scanlation groups whose chapters are hidden everywhere.
+    pub blocked_groups: Vec<GroupRef>,
+    /// Preferred scanlation group per manga, keyed by manga ID.
+    pub preferred_groups: HashMap<String, GroupRef>,
+    /// Chapter languages in priority order; later ones fill gaps in earlier ones.
+    pub languages: Vec<String>,
+    /// Title languages in priority order; any available title is used when none match.
+    pub title_languages: Vec<String>,
+    /// Whether to report page download outcomes (URL, size and timing) to
+    /// the MangaDex@Home network, which uses them to retire bad nodes. Off
+    /// unless the user opts in. Read at startup.
+    pub at_home_reports: bool,
+    /// Page quality: originals, data saver, or automatic by download speed.
+    pub quality_mode: QualityMode,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            blocked_groups: Vec::new(),
+            preferred_groups: HashMap::new(),
+            languages: vec!["en".to_string()],
+            title_languages: ["en", "ja-ro", "ja"].map(String::from).to_vec(),
+            at_home_reports: false,
+            quality_mode: QualityMode::Original,
+        }
+    }
+}
+
+/// Chapter languages offered in the language panel, besides any already chosen.
+pub const CHAPTER_LANGUAGES: [&str; 14] = [
+    "en", "es-la", "es", "pt-br", "pt", "fr", "it", "de", "ru", "id", "vi", "pl", "tr", "ja",
+];
+
+/// A scanlation group, with its name kept for display.
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct GroupRef {
+    /// The group's MangaDex ID.
+    pub id: String,
+    /// The group's name when it was saved.
+    pub name: String,
+}
+
+impl Settings {
+    /// Loads the settings file, falling back to defaults when it is missing or unreadable.
+    pub fn load() -> Self {
+        settings_path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Writes the settings file, creating its directory if needed.
+    pub fn save(&self) -> std::io::Result<()> {
+        let Some(path) = settings_path() else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, json)
+    }
+
+    /// IDs of every blocked group.
+    pub fn blocked_group_ids(&self) -> Vec<String> {
+        self.blocked_groups.iter().map(|g| g.id.clone()).collect()
+    }
+
+    /// Rows of the language panel: chosen languages in priority order, then
+    /// the rest of [`CHAPTER_LANGUAGES`].
+    pub fn language_rows(&self) -> Vec<String> {
+        let mut rows = self.languages.clone();
+        rows.extend(
+            CHAPTER_LANGUAGES
+                .iter()
+                .map(|l| l.to_string())
+                .filter(|l| !self.languages.contains(l)),
+        );
+        rows
+    }
+
+    /// Adds `language` to the end of the priority list, or removes it if it
+    /// is already chosen. The last language cannot be removed.
+    pub fn toggle_language(&mut self, language: &str) {
+        if let Some(i) = self.languages.iter().position(|l| l == language) {
+            if self.languages.len() > 1 {
+                self.languages.remove(i);
+            }
+        } else {
+            self.languages.push(language.to_string());
+        }
+    }
+
+    /// Moves a chosen language one step up (`earlier`) or down the priority list.
+    /// Returns `false` if it could not move.
+    pub fn move_language(&mut self, language: &str, earlier: bool) -> bool {
+        let Some(i) = self.languages.iter().position(|l| l == language) else {
+            return false;
+        };
+        let target = if earlier {
+            i.checked_sub(1)
+        } else {
+            Some(i + 1)
+        };
+        match target {
+            Some(j) if j < self.languages.len() => {
+                self.languages.swap(i, j);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Location of the settings file, or `None` if no home directory is known.
+fn settings_path() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_dir.join("yomu").join("settings.json"))
+}
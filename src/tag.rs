@@ -0,0 +1,160 @@
+use crate::client::MangaDexClient;
+use crate::error::Result;
+use crate::ratelimit::Endpoint;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// A client for fetching the MangaDex tag catalogue.
+#[derive(Clone)]
+pub struct TagClient {
+    /// The parent `MangaDexClient` (a cheap clone sharing its connection pool).
+    pub client: MangaDexClient,
+}
+
+impl TagClient {
+    /// Fetches every tag MangaDex knows about, sorted by group and then English name.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use yomu::MangaDexClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = MangaDexClient::new()?;
+    /// let tags = client.tag_client().fetch_tags().await?;
+    /// for tag in tags.iter().take(5) {
+    ///     println!("{} ({})", tag.name("en").unwrap_or("?"), tag.attributes.group);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_tags(&self) -> Result<Vec<Tag>> {
+        let request = self
+            .client
+            .http_client()
+            .get(format!("{}/manga/tag", self.client.base_url()));
+        let resp = self.client.send(Endpoint::Api, request).await?;
+        let mut tags = resp.json::<TagResponse>().await?.data;
+        tags.sort_by(|a, b| {
+            group_rank(&a.attributes.group)
+                .cmp(&group_rank(&b.attributes.group))
+                .then_with(|| a.name("en").cmp(&b.name("en")))
+        });
+        Ok(tags)
+    }
+}
+
+/// Display order of tag groups: genres first, content warnings last.
+fn group_rank(group: &str) -> usize {
+    TAG_GROUPS
+        .iter()
+        .position(|g| *g == group)
+        .unwrap_or(TAG_GROUPS.len())
+}
+
+/// Tag groups MangaDex uses, in display order.
+pub const TAG_GROUPS: [&str; 4] = ["genre", "theme", "format", "content"];
+
+/// Response from the MangaDex API for the tag list.
+#[derive(Deserialize)]
+pub struct TagResponse {
+    /// Result status of the request.
+    pub result: String,
+    /// Type of the response.
+    pub response: String,
+    /// Every tag.
+    pub data: Vec<Tag>,
+    /// Number of items returned.
+    pub limit: usize,
+    /// Number of items skipped.
+    pub offset: usize,
+    /// Total number of tags.
+    pub total: usize,
+}
+
+/// A single MangaDex tag, such as "Action" or "Isekai".
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct Tag {
+    /// Unique identifier for the tag, used in `includedTags[]`/`excludedTags[]`.
+    pub id: String,
+    /// Resource type (usually "tag").
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// Attributes containing tag information.
+    pub attributes: TagAttributes,
+}
+
+impl Tag {
+    /// Returns the tag's name in `language`, if MangaDex has one.
+    pub fn name(&self, language: &str) -> Option<&str> {
+        self.attributes.name.get(language).map(|n| n.as_str())
+    }
+}
+
+/// Attributes associated with a tag.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct TagAttributes {
+    /// Map of names in different languages.
+    pub name: HashMap<String, String>,
+    /// The group the tag belongs to: `genre`, `theme`, `format` or `content`.
+    pub group: String,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::retry::RetryPolicy;
+    use crate::test_support::serve_once;
+
+    fn tag(id: &str, group: &str, names: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "type": "tag",
+            "attributes": { "name": names, "group": group }
+        })
+    }
+
+    #[tokio::test]
+    async fn fetch_tags_sorts_by_group_then_name() {
+        let body = serde_json::json!({
+            "result": "ok",
+            "response": "collection",
+            "data": [
+                tag("gore", "content", serde_json::json!({ "en": "Gore" })),
+                tag("isekai", "theme", serde_json::json!({ "en": "Isekai" })),
+                tag("romance", "genre", serde_json::json!({ "en": "Romance", "fr": "Romance" })),
+                tag("oneshot", "format", serde_json::json!({ "en": "Oneshot" })),
+                tag("action", "genre", serde_json::json!({ "en": "Action" })),
+                tag("other", "unknown", serde_json::json!({ "ja": "その他" })),
+            ],
+            "limit": 6,
+            "offset": 0,
+            "total": 6
+        })
+        .to_string();
+        let (base_url, _server) = serve_once(format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{body}",
+            body.len()
+        ))
+        .await;
+        let client = MangaDexClient::builder()
+            .base_url(base_url)
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .unwrap();
+
+        let tags = client.tag_client().fetch_tags().await.unwrap();
+        let ids: Vec<&str> = tags.iter().map(|t| t.id.as_str()).collect();
+        // Known groups in display order, then anything MangaDex adds later.
+        assert_eq!(
+            ids,
+            ["action", "romance", "isekai", "oneshot", "gore", "other"]
+        );
+
+        // Names in a missing language are `None`, for callers to fall back on.
+        assert_eq!(tags[1].name("fr"), Some("Romance"));
+        assert_eq!(tags[0].name("fr"), None);
+        assert_eq!(tags[5].name("en"), None);
+        assert_eq!(tags[5].name("ja"), Some("その他"));
+    }
+}
@@ -0,0 +1,85 @@
+//! Helpers shared by the unit tests.
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+/// Serves `response` to the first request on a local port.
+///
+/// Returns the server's base URL (`http://127.0.0.1:<port>`) and a handle
+/// that resolves to the raw request once the response has been written.
+pub(crate) async fn serve_once(response: impl Into<String>) -> (String, JoinHandle<String>) {
+    let (listener, base_url) = bind().await;
+    let response = response.into();
+    let server = tokio::spawn(async move { answer(listener, vec![response]).await.remove(0) });
+    (base_url, server)
+}
+
+/// Serves `responses` to consecutive requests on a local port, over as many
+/// connections as the client opens.
+///
+/// Returns the server's base URL and a handle that resolves to the raw
+/// requests once every response has been written.
+pub(crate) async fn serve(responses: Vec<String>) -> (String, JoinHandle<Vec<String>>) {
+    let (listener, base_url) = bind().await;
+    (base_url, tokio::spawn(answer(listener, responses)))
+}
+
+async fn bind() -> (TcpListener, String) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    (listener, base_url)
+}
+
+async fn answer(listener: TcpListener, responses: Vec<String>) -> Vec<String> {
+    let mut socket: Option<TcpStream> = None;
+    let mut requests = Vec::new();
+    for response in responses {
+        let request = loop {
+            let stream = match &mut socket {
+                Some(stream) => stream,
+                None => socket.insert(listener.accept().await.unwrap().0),
+            };
+            match read_request(stream).await {
+                Some(request) => break request,
+                // The client closed the connection; wait for the next one.
+                None => socket = None,
+            }
+        };
+        let stream = socket.as_mut().expect("a request was just read");
+        stream.write_all(response.as_bytes()).await.unwrap();
+        requests.push(String::from_utf8_lossy(&request).into_owned());
+    }
+    requests
+}
+
+/// Reads one request, or returns `None` if the connection closes before
+/// any of it arrives.
+async fn read_request(stream: &mut TcpStream) -> Option<Vec<u8>> {
+    let mut request = Vec::new();
+    let mut buf = [0; 1024];
+    while !is_complete(&request) {
+        let n = stream.read(&mut buf).await.unwrap();
+        if n == 0 {
+            assert!(request.is_empty(), "connection closed mid-request");
+            return None;
+        }
+        request.extend_from_slice(&buf[..n]);
+    }
+    Some(request)
+}
+
+/// Returns `true` once `request` holds the headers and a body as long as
+/// its `content-length`.
+fn is_complete(request: &[u8]) -> bool {
+    let Some(end) = request.windows(4).position(|w| w == b"\r\n\r\n") else {
+        return false;
+    };
+    let headers = String::from_utf8_lossy(&request[..end]).to_ascii_lowercase();
+    let body_len = headers
+        .lines()
+        .find_map(|line| line.strip_prefix("content-length:"))
+        .and_then(|len| len.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+    request.len() >= end + 4 + body_len
+}